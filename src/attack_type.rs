@@ -1,3 +1,4 @@
+use crate::weapon::Coating;
 use crate::{AttackIntent, RangeType};
 use derivative::Derivative;
 use rltk::Point;
@@ -19,23 +20,110 @@ pub enum AttackType {
     Recover,
     // lance
     LanceDraw,
-    LanceThrust { level: u8, dest: Point },
-    LanceCharge { dir: crate::Direction },
+    LanceThrust {
+        level: u8,
+        dest: Point,
+    },
+    LanceCharge {
+        dir: crate::Direction,
+    },
     LanceSweep,
+    // great sword
+    GreatSwordSlash,
+    GreatSwordCharge {
+        level: u8,
+        dest: Point,
+    },
+    // hammer
+    HammerSmash {
+        level: u8,
+    },
+    // bow
+    BowShot {
+        dir: crate::Direction,
+        coating: Option<Coating>,
+    },
+    BowPowerShot {
+        dir: crate::Direction,
+        coating: Option<Coating>,
+    },
+    ArrowHit {
+        damage: i32,
+        coating: Option<Coating>,
+    },
+    // items
+    FlashBomb,
     // enemy specific attacks
     Haymaker,
     Ranged,
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum DamageKind {
+    Cut,
+    Blunt,
+    Pierce,
+    Elemental,
+}
+
+impl DamageKind {
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            DamageKind::Cut => "C",
+            DamageKind::Blunt => "B",
+            DamageKind::Pierce => "P",
+            DamageKind::Elemental => "E",
+        }
+    }
+}
+
 #[derive(PartialEq, Copy, Clone)]
 pub enum AttackTrait {
-    Damage { amount: i32 },
-    Knockback { amount: i32 },
+    Damage {
+        amount: i32,
+    },
+    Knockback {
+        amount: i32,
+    },
     Movement,
-    Heal { amount: i32 },
-    Invulnerable { duration: u32 },
-    LanceCharge { dir: crate::Direction },
-    NeedsStamina { amount: i32 },
+    Heal {
+        amount: i32,
+    },
+    Invulnerable {
+        duration: u32,
+    },
+    LanceCharge {
+        dir: crate::Direction,
+    },
+    NeedsStamina {
+        amount: i32,
+    },
+    Projectile {
+        dir: crate::Direction,
+        speed: i32,
+        range: i32,
+        hit: AttackType,
+    },
+    Poison {
+        amount: i32,
+        duration: u32,
+    },
+    Paralyze {
+        duration: u32,
+    },
+    Sleep {
+        duration: u32,
+    },
+    Bleed {
+        amount: i32,
+        duration: u32,
+    },
+    Stun {
+        amount: i32,
+    },
+    Daze {
+        turns: u32,
+    },
 }
 
 // check if an attack is can be executed
@@ -55,6 +143,20 @@ pub fn is_attack_valid(attack_type: AttackType, from_point: Point, target: Point
     None
 }
 
+// the furthest distance away an attack can hit something
+pub fn get_attack_reach(attack_type: AttackType) -> i32 {
+    let range_type = get_attack_range(attack_type);
+    let shape = get_attack_shape(attack_type);
+    let origin = Point::new(0, 0);
+
+    crate::range_type::resolve_range_at(&range_type, origin)
+        .iter()
+        .flat_map(|tile| crate::range_type::resolve_range_at(&shape, *tile))
+        .map(|point| std::cmp::max(point.x.abs(), point.y.abs()))
+        .max()
+        .unwrap_or(0)
+}
+
 // return all points that are affected by an attack
 pub fn each_attack_target(attack_type: AttackType, from_point: Point) -> Vec<Point> {
     let shape = get_attack_shape(attack_type);
@@ -89,6 +191,13 @@ pub fn get_attack_range(attack_type: AttackType) -> RangeType {
         AttackType::LanceThrust { .. } => RangeType::Square { size: 1 },
         AttackType::LanceCharge { .. } => RangeType::Single,
         AttackType::LanceSweep => RangeType::Square { size: 1 },
+        AttackType::GreatSwordSlash => RangeType::Square { size: 1 },
+        AttackType::GreatSwordCharge { .. } => RangeType::Square { size: 1 },
+        AttackType::HammerSmash { .. } => RangeType::Square { size: 1 },
+        AttackType::BowShot { .. } => RangeType::Single,
+        AttackType::BowPowerShot { .. } => RangeType::Single,
+        AttackType::ArrowHit { .. } => RangeType::Single,
+        AttackType::FlashBomb => RangeType::Single,
     }
 }
 
@@ -106,6 +215,13 @@ pub fn get_attack_power(attack_type: AttackType) -> i32 {
         AttackType::LanceThrust { level, .. } => level as i32,
         AttackType::LanceCharge { .. } => 0,
         AttackType::LanceSweep => 2,
+        AttackType::GreatSwordSlash => 2,
+        AttackType::GreatSwordCharge { level, .. } => get_charge_damage(level),
+        AttackType::HammerSmash { level } => level as i32 + 1,
+        AttackType::BowShot { .. } => 1,
+        AttackType::BowPowerShot { .. } => 3,
+        AttackType::ArrowHit { damage, .. } => damage,
+        AttackType::FlashBomb => 0,
     }
 }
 
@@ -123,6 +239,58 @@ pub fn get_attack_shape(attack_type: AttackType) -> RangeType {
         AttackType::LanceThrust { dest, .. } => RangeType::Path { dest },
         AttackType::LanceCharge { .. } => RangeType::Single,
         AttackType::LanceSweep => RangeType::Square { size: 1 },
+        AttackType::GreatSwordSlash => RangeType::Single,
+        AttackType::GreatSwordCharge { level, dest } => {
+            if level < 3 {
+                RangeType::Path { dest }
+            } else {
+                RangeType::Square { size: 1 }
+            }
+        }
+        AttackType::HammerSmash { .. } => RangeType::Single,
+        AttackType::BowShot { .. } => RangeType::Single,
+        AttackType::BowPowerShot { .. } => RangeType::Single,
+        AttackType::ArrowHit { .. } => RangeType::Single,
+        AttackType::FlashBomb => RangeType::Square { size: 4 },
+    }
+}
+
+// charged attacks get stronger the longer they are held
+fn get_charge_damage(level: u8) -> i32 {
+    2 + 2 * level as i32
+}
+
+pub fn get_damage_kind(attack_type: AttackType) -> DamageKind {
+    match attack_type {
+        AttackType::Sweep => DamageKind::Cut,
+        AttackType::Ranged => DamageKind::Pierce,
+        AttackType::LanceDraw => DamageKind::Pierce,
+        AttackType::LanceThrust { .. } => DamageKind::Pierce,
+        AttackType::LanceCharge { .. } => DamageKind::Pierce,
+        AttackType::LanceSweep => DamageKind::Cut,
+        AttackType::GreatSwordSlash => DamageKind::Cut,
+        AttackType::GreatSwordCharge { .. } => DamageKind::Cut,
+        AttackType::BowShot { .. } => DamageKind::Pierce,
+        AttackType::BowPowerShot { .. } => DamageKind::Pierce,
+        AttackType::ArrowHit { .. } => DamageKind::Pierce,
+        AttackType::FlashBomb => DamageKind::Elemental,
+        _ => DamageKind::Blunt,
+    }
+}
+
+// how far away monsters can hear an attack
+pub fn get_noise(attack_type: AttackType) -> i32 {
+    match attack_type {
+        AttackType::Dodge => 3,
+        AttackType::Recover => 0,
+        AttackType::LanceCharge { .. } => 6,
+        AttackType::GreatSwordCharge { level, .. } => 4 + level as i32,
+        AttackType::HammerSmash { level } => 4 + level as i32,
+        AttackType::BowShot { .. } => 2,
+        AttackType::BowPowerShot { .. } => 3,
+        AttackType::ArrowHit { .. } => 2,
+        AttackType::FlashBomb => 8,
+        _ => 4,
     }
 }
 
@@ -212,6 +380,13 @@ pub fn get_attack_name(attack_type: AttackType) -> String {
         AttackType::LanceThrust { .. } => "Thrust",
         AttackType::LanceCharge { .. } => "Charge",
         AttackType::LanceSweep => "Sweep",
+        AttackType::GreatSwordSlash => "Slash",
+        AttackType::GreatSwordCharge { .. } => "Charged Slash",
+        AttackType::HammerSmash { .. } => "Smash",
+        AttackType::BowShot { .. } => "Shoot",
+        AttackType::BowPowerShot { .. } => "Heavy Shot",
+        AttackType::ArrowHit { .. } => "Arrow",
+        AttackType::FlashBomb => "Flash Bomb",
     }
     .to_string()
 }
@@ -219,9 +394,16 @@ pub fn get_attack_name(attack_type: AttackType) -> String {
 use AttackTrait::*;
 pub fn get_attack_traits(attack_type: AttackType) -> Vec<AttackTrait> {
     match attack_type {
-        AttackType::Sweep => vec![Damage { amount: 2 }],
+        AttackType::Sweep => vec![
+            Damage { amount: 2 },
+            Bleed {
+                amount: 1,
+                duration: 3,
+            },
+            Knockback { amount: 1 },
+        ],
         AttackType::Punch => vec![Damage { amount: 1 }],
-        AttackType::Stun => vec![Damage { amount: 0 }],
+        AttackType::Stun => vec![Stun { amount: 4 }],
         AttackType::Push => vec![Knockback { amount: 2 }],
         AttackType::Dodge => vec![
             Movement,
@@ -239,5 +421,48 @@ pub fn get_attack_traits(attack_type: AttackType) -> Vec<AttackTrait> {
         }],
         AttackType::LanceCharge { dir } => vec![LanceCharge { dir }],
         AttackType::LanceSweep => vec![Damage { amount: 2 }],
+        AttackType::GreatSwordSlash => vec![Damage { amount: 2 }],
+        AttackType::GreatSwordCharge { level, .. } => vec![Damage {
+            amount: get_charge_damage(level),
+        }],
+        AttackType::HammerSmash { level } => vec![
+            Damage {
+                amount: level as i32 + 1,
+            },
+            Stun {
+                amount: 2 * level as i32,
+            },
+        ],
+        AttackType::BowShot { dir, coating } => vec![Projectile {
+            dir,
+            speed: 2,
+            range: 10,
+            hit: AttackType::ArrowHit { damage: 1, coating },
+        }],
+        AttackType::BowPowerShot { dir, coating } => vec![Projectile {
+            dir,
+            speed: 3,
+            range: 14,
+            hit: AttackType::ArrowHit { damage: 3, coating },
+        }],
+        AttackType::ArrowHit { damage, coating } => get_arrow_traits(damage, coating),
+        AttackType::FlashBomb => vec![Daze { turns: 2 }],
+    }
+}
+
+// coatings change what happens when an arrow lands
+fn get_arrow_traits(damage: i32, coating: Option<Coating>) -> Vec<AttackTrait> {
+    match coating {
+        None => vec![Damage { amount: damage }],
+        Some(Coating::Power) => vec![Damage { amount: damage + 2 }],
+        Some(Coating::Poison) => vec![
+            Damage { amount: damage },
+            Poison {
+                amount: 1,
+                duration: 5,
+            },
+        ],
+        Some(Coating::Paralysis) => vec![Damage { amount: damage }, Paralyze { duration: 2 }],
+        Some(Coating::Sleep) => vec![Damage { amount: damage }, Sleep { duration: 4 }],
     }
 }
@@ -0,0 +1,134 @@
+use crate::sys_ai::AiSystem;
+use crate::sys_mapindex::MapIndexSystem;
+use crate::sys_movement::MovementSystem;
+use crate::Behavior;
+use rltk::Point;
+use specs::prelude::*;
+
+const MONSTERS: i32 = 24;
+const TURNS: u32 = 50;
+const WIDTH: i32 = 80;
+const HEIGHT: i32 = 50;
+
+// times monster turns on an open map with a mix of small and wide monsters chasing the player
+// run with `cargo run --release --features bench -- --bench`
+pub fn ai_turns_with_many_monsters() {
+    let mut world = World::new();
+    System::setup(&mut AiSystem, &mut world);
+    System::setup(&mut MovementSystem, &mut world);
+    System::setup(&mut MapIndexSystem, &mut world);
+
+    let mut rng = rltk::RandomNumberGenerator::seeded(0);
+    let mut map = crate::Map::new(
+        WIDTH,
+        HEIGHT,
+        &"Bench".to_string(),
+        &"#FFFFFF".to_string(),
+        &mut rng,
+    );
+    for x in 1..WIDTH - 1 {
+        for y in 1..HEIGHT - 1 {
+            let index = map.get_index(x, y);
+            map.tiles[index] = crate::TileType::Floor;
+        }
+    }
+    map.set_blocked_tiles();
+    world.insert(map);
+    world.insert(crate::ParticleBuilder::new());
+    world.insert(rng);
+
+    let player = world
+        .create_entity()
+        .with(crate::Position {
+            x: WIDTH / 2,
+            y: HEIGHT / 2,
+        })
+        .with(crate::BlocksTile)
+        .build();
+    world.insert(player);
+
+    let mut visible = Vec::new();
+    for x in 0..WIDTH {
+        for y in 0..HEIGHT {
+            visible.push(Point::new(x, y));
+        }
+    }
+
+    let mut monsters = Vec::new();
+    for i in 0..MONSTERS {
+        let point = Point::new(4 + (i % 12) * 6, if i < 12 { 4 } else { HEIGHT - 5 });
+
+        // every other monster is three tiles wide
+        let mut builder = world
+            .create_entity()
+            .with(crate::Position {
+                x: point.x,
+                y: point.y,
+            })
+            .with(crate::BlocksTile)
+            .with(crate::AiState {
+                status: Behavior::Wander,
+            })
+            .with(crate::Viewshed {
+                visible: visible.clone(),
+                dirty: false,
+                range: 100,
+            })
+            .with(crate::Moveset {
+                moves: vec![(crate::AttackType::Punch, 1.0)],
+                bump_attack: crate::AttackType::Punch,
+            });
+
+        if i % 2 == 0 {
+            let part_list = vec![crate::MonsterPart {
+                symbol_map: std::collections::HashMap::from([
+                    (Point::new(-1, 0), rltk::to_cp437('<')),
+                    (Point::new(1, 0), rltk::to_cp437('>')),
+                ]),
+                health: 2,
+                max_health: 2,
+                kind: crate::PartKind::Body,
+                hitzone: crate::Hitzone::default(),
+                discovered: vec![],
+                on_break: vec![],
+                broken: false,
+            }];
+            builder = builder.with(crate::MultiTile {
+                bounds: crate::all_bounds(&part_list),
+                part_list,
+            });
+        }
+
+        monsters.push(builder.build());
+    }
+
+    MapIndexSystem.run_now(&world);
+
+    let start = std::time::Instant::now();
+    for _ in 0..TURNS {
+        for ent in monsters.iter() {
+            world
+                .write_storage::<crate::CanActFlag>()
+                .insert(
+                    *ent,
+                    crate::CanActFlag {
+                        is_reaction: false,
+                        reaction_target: None,
+                    },
+                )
+                .expect("Failed to insert CanActFlag");
+        }
+
+        // monsters moving invalidates the cached flow fields every turn
+        AiSystem.run_now(&world);
+        MovementSystem.run_now(&world);
+        MapIndexSystem.run_now(&world);
+        world.maintain();
+
+        // nobody gets hurt in the benchmark
+        world.write_storage::<crate::AttackIntent>().clear();
+    }
+
+    let elapsed = start.elapsed();
+    println!("{} monsters: {:?} per AI turn", MONSTERS, elapsed / TURNS);
+}
@@ -108,6 +108,10 @@ pub fn particle_hit_color() -> RGB {
     rltk::RGB::named(rltk::RED)
 }
 
+pub fn projectile_color() -> RGB {
+    RGB::named(rltk::WHEAT)
+}
+
 pub fn health_color() -> RGB {
     RGB::named(rltk::MAGENTA)
 }
@@ -63,6 +63,13 @@ pub struct Health {
     pub max: i32,
 }
 
+impl Health {
+    // healing never goes past max health
+    pub fn heal(&mut self, amount: i32) {
+        self.current = std::cmp::min(self.max, self.current + amount);
+    }
+}
+
 #[derive(Component)]
 pub struct Stamina {
     pub current: i32,
@@ -105,16 +112,31 @@ pub struct ViewableIndex {
 #[derive(Component)]
 pub struct AttackInProgress;
 
+// the player took damage since their last turn
+#[derive(Component)]
+pub struct HitFlag;
+
 #[derive(Component)]
 pub struct BlockAttack {
     pub block_amount: u32,
+    pub perfect_window: u32,
+    pub countered: bool,
+}
+
+// bonuses from the player's equipment
+#[derive(Component, Default)]
+pub struct StatBonus {
+    pub attack: i32,
+    pub defense: i32,
+    // extra ticks of invulnerability when dodging
+    pub evade: u32,
+    // stamina saved on each dodge
+    pub stamina_discount: i32,
 }
 
 #[derive(Component)]
 pub struct AiState {
     pub status: crate::Behavior,
-    pub prev_path: Option<rltk::NavigationPath>,
-    pub path_step: usize,
 }
 
 #[derive(Component)]
@@ -125,6 +147,12 @@ pub struct Heal {
 #[derive(Component)]
 pub struct Item;
 
+// a severed part that can be carved for materials from the named monster
+#[derive(Component)]
+pub struct Carveable {
+    pub monster: String,
+}
+
 #[derive(Component)]
 pub struct Openable;
 
@@ -154,6 +182,7 @@ pub enum NpcType {
     Blacksmith,
     Shopkeeper,
     Handler,
+    EquipmentBox,
 }
 
 #[derive(Component, Debug)]
@@ -162,9 +191,53 @@ pub struct Npc {
 }
 
 #[derive(Component)]
-pub struct Invulnerable {
-    pub duration: u32,
+pub struct MissionTarget;
+
+// how much a monster has noticed the player
+#[derive(Component, Default)]
+pub struct Awareness {
+    pub alert: i32,
+}
+
+// recent movement results, so monsters can tell when they're jammed somewhere
+#[derive(Component, Default)]
+pub struct StuckInfo {
+    pub failed_moves: u32,
+    pub clear_moves: u32,
+    pub recoveries: u32,
+    pub blocked_dir: Option<rltk::Point>,
+    // part movements applied to fit through a gap, written for a monster facing north
+    pub squeezed: Option<Vec<rltk::Point>>,
 }
 
+// minor monsters that hunt together, the leader is a member of its own pack
 #[derive(Component)]
-pub struct MissionTarget;
+pub struct PackMember {
+    pub leader: Entity,
+    // which side of the player to flank from
+    pub slot: usize,
+    // turns spent holding position for the rest of the pack
+    pub wait: u32,
+}
+
+// where a monster runs to when it's hurt
+#[derive(Component)]
+pub struct Nest {
+    pub point: rltk::Point,
+}
+
+#[derive(Component)]
+pub struct Projectile {
+    pub dir: crate::Direction,
+    pub speed: i32,
+    pub range: i32,
+    pub source: Entity,
+    pub hit: crate::AttackType,
+}
+
+// builds up from blunt hits, monsters are stunned once it fills
+#[derive(Component)]
+pub struct StunMeter {
+    pub current: i32,
+    pub threshold: i32,
+}
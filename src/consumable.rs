@@ -0,0 +1,70 @@
+use crate::weapon::Coating;
+use derivative::Derivative;
+
+pub const SHARPEN_DURATION: u32 = 20;
+pub const POTION_HEAL: i32 = 3;
+pub const MEGA_POTION_HEAL: i32 = 8;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Derivative)]
+#[derivative(Hash)]
+pub enum Consumable {
+    Potion,
+    MegaPotion,
+    StaminaRation,
+    FlashBomb,
+    Whetstone,
+    Coating(Coating),
+}
+
+// everything the shop keeps in stock, in the order it is listed
+pub const SHOP_STOCK: [Consumable; 9] = [
+    Consumable::Potion,
+    Consumable::MegaPotion,
+    Consumable::StaminaRation,
+    Consumable::FlashBomb,
+    Consumable::Whetstone,
+    Consumable::Coating(Coating::Power),
+    Consumable::Coating(Coating::Poison),
+    Consumable::Coating(Coating::Paralysis),
+    Consumable::Coating(Coating::Sleep),
+];
+
+impl Consumable {
+    pub fn name(&self) -> String {
+        match self {
+            Consumable::Potion => "Potion".to_string(),
+            Consumable::MegaPotion => "Mega Potion".to_string(),
+            Consumable::StaminaRation => "Ration".to_string(),
+            Consumable::FlashBomb => "Flash Bomb".to_string(),
+            Consumable::Whetstone => "Whetstone".to_string(),
+            Consumable::Coating(coating) => coating.name(),
+        }
+    }
+
+    pub fn price(&self) -> u32 {
+        match self {
+            Consumable::Potion => 30,
+            Consumable::MegaPotion => 80,
+            Consumable::StaminaRation => 40,
+            Consumable::FlashBomb => 60,
+            Consumable::Whetstone => 50,
+            Consumable::Coating(_) => 40,
+        }
+    }
+
+    // the shop only buys items back at half price
+    pub fn sell_price(&self) -> u32 {
+        self.price() / 2
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            Consumable::Potion => format!("Heals {} health", POTION_HEAL),
+            Consumable::MegaPotion => format!("Heals {} health", MEGA_POTION_HEAL),
+            Consumable::StaminaRation => "Restores all stamina".to_string(),
+            Consumable::FlashBomb => "Dazes nearby monsters".to_string(),
+            Consumable::Whetstone => "Attacks deal +1 damage".to_string(),
+            Consumable::Coating(_) => "Loaded into a bow".to_string(),
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+lazy_static! {
+    static ref ARMOR_DATA: ArmorData = load_armor_data();
+}
+
+#[derive(Deserialize)]
+struct ArmorData {
+    armor: Vec<ArmorInfo>,
+}
+
+#[derive(Deserialize)]
+pub struct ArmorInfo {
+    pub name: String,
+    pub slot: ArmorSlot,
+    pub defense: i32,
+    #[serde(default)]
+    pub skills: HashMap<Skill, u32>,
+}
+
+#[derive(Deserialize, Copy, Clone, PartialEq, Debug)]
+pub enum ArmorSlot {
+    Head,
+    Chest,
+    Arms,
+    Waist,
+    Legs,
+}
+
+pub const ALL_SLOTS: [ArmorSlot; 5] = [
+    ArmorSlot::Head,
+    ArmorSlot::Chest,
+    ArmorSlot::Arms,
+    ArmorSlot::Waist,
+    ArmorSlot::Legs,
+];
+
+#[derive(Deserialize, Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Skill {
+    EvadeWindow,
+    GuardUp,
+    Constitution,
+    AttackBoost,
+}
+
+pub const ALL_SKILLS: [Skill; 4] = [
+    Skill::EvadeWindow,
+    Skill::GuardUp,
+    Skill::Constitution,
+    Skill::AttackBoost,
+];
+
+impl Skill {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Skill::EvadeWindow => "Evade Window",
+            Skill::GuardUp => "Guard Up",
+            Skill::Constitution => "Constitution",
+            Skill::AttackBoost => "Attack Boost",
+        }
+    }
+}
+
+rltk::embedded_resource!(ARMOR_RAW_DATA, "../../data/armor_info.yaml");
+
+fn load_armor_data() -> ArmorData {
+    rltk::link_resource!(ARMOR_RAW_DATA, "../../data/armor_info.yaml");
+
+    // Retrieve the raw data as an array of u8 (8-bit unsigned chars)
+    let raw_data = rltk::embedding::EMBED
+        .lock()
+        .get_resource("../../data/armor_info.yaml".to_string())
+        .unwrap();
+    let raw_string =
+        std::str::from_utf8(raw_data).expect("Unable to convert to a valid UTF-8 string.");
+
+    serde_yaml::from_str(raw_string).expect("Unable to parse file")
+}
+
+pub fn get_armor_info(name: &str) -> Option<&'static ArmorInfo> {
+    ARMOR_DATA.armor.iter().find(|info| info.name == name)
+}
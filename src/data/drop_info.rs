@@ -0,0 +1,72 @@
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct DropTable {
+    // how many materials are carved from a kill
+    pub carves: u32,
+    pub materials: Vec<WeightedMaterial>,
+    // each broken part guarantees one of these
+    #[serde(default)]
+    pub rare_materials: Vec<WeightedMaterial>,
+}
+
+#[derive(Deserialize)]
+pub struct WeightedMaterial {
+    pub name: String,
+    pub weight: u32,
+}
+
+// the drop table carried by each monster definition in data/monster_info.yaml
+pub fn get_drop_info(name: &str) -> Option<&'static DropTable> {
+    super::get_monster_info(name).and_then(|info| info.drops.as_ref())
+}
+
+fn roll_material(
+    table: &'static [WeightedMaterial],
+    rng: &mut rltk::RandomNumberGenerator,
+) -> Option<&'static String> {
+    let total: u32 = table.iter().map(|mat| mat.weight).sum();
+    if total == 0 {
+        return None;
+    }
+
+    let mut roll = rng.range(0, total);
+    for mat in table {
+        if roll < mat.weight {
+            return Some(&mat.name);
+        }
+
+        roll -= mat.weight;
+    }
+
+    None
+}
+
+impl DropTable {
+    // materials gained from killing a monster with the given number of broken parts
+    pub fn roll_drops(
+        &'static self,
+        broken_parts: usize,
+        rng: &mut rltk::RandomNumberGenerator,
+    ) -> Vec<&'static String> {
+        let mut drops = Vec::new();
+
+        for _ in 0..self.carves {
+            drops.extend(roll_material(&self.materials, rng));
+        }
+
+        for _ in 0..broken_parts {
+            drops.extend(roll_material(&self.rare_materials, rng));
+        }
+
+        drops
+    }
+
+    // a severed part is carved for one rare material
+    pub fn roll_severed(
+        &'static self,
+        rng: &mut rltk::RandomNumberGenerator,
+    ) -> Option<&'static String> {
+        roll_material(&self.rare_materials, rng)
+    }
+}
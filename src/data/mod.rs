@@ -1,2 +1,16 @@
 mod area_info;
+mod armor_info;
+mod drop_info;
+mod monster_info;
+mod recipe_info;
+mod upgrade_info;
+mod weapon_info;
 pub use area_info::{get_random_area, AreaInfo};
+pub use armor_info::{get_armor_info, ArmorInfo, Skill, ALL_SKILLS, ALL_SLOTS};
+pub use drop_info::get_drop_info;
+pub use monster_info::{get_monster_info, get_monsters, MonsterInfo};
+pub use recipe_info::{get_recipes, RecipeInfo, RecipeKind};
+pub use upgrade_info::{get_upgrades, UpgradeCost, UpgradeInfo, UpgradeKind};
+pub use weapon_info::{
+    get_weapon_info, AttackKind, AttackOrigin, WeaponAttack, WeaponInfo, WeaponState,
+};
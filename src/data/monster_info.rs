@@ -0,0 +1,131 @@
+use super::drop_info::DropTable;
+use crate::{AttackType, BreakEffect, Hitzone, PartKind};
+use serde::Deserialize;
+
+lazy_static! {
+    static ref MONSTER_DATA: MonsterData = load_monster_data();
+}
+
+#[derive(Deserialize)]
+struct MonsterData {
+    monsters: Vec<MonsterInfo>,
+}
+
+#[derive(Deserialize)]
+pub struct MonsterInfo {
+    pub name: String,
+    // minor monsters aren't quest targets and spawn in packs
+    #[serde(default)]
+    pub minor: bool,
+    // how much this monster adds to a quest's difficulty
+    pub difficulty: i32,
+    pub glyph: String,
+    pub fg: String,
+    #[serde(default)]
+    pub description: Vec<String>,
+    pub health: i32,
+    // how quickly the monster's turns come around
+    pub speed: i32,
+    pub vision: i32,
+    // whether the monster turns to face where it moves
+    #[serde(default)]
+    pub facing: bool,
+    pub moves: Vec<WeightedAttack>,
+    pub bump_attack: MonsterAttack,
+    #[serde(default)]
+    pub parts: Vec<PartInfo>,
+    // materials carved from the monster, if it leaves anything worth carving
+    pub drops: Option<DropTable>,
+}
+
+#[derive(Deserialize)]
+pub struct WeightedAttack {
+    pub attack: MonsterAttack,
+    pub weight: f32,
+}
+
+// the attacks a monster can be given, these don't depend on where the monster is
+#[derive(Deserialize, Copy, Clone)]
+pub enum MonsterAttack {
+    Sweep,
+    Punch,
+    Stun,
+    Push,
+    Haymaker,
+    Ranged,
+}
+
+#[derive(Deserialize)]
+pub struct PartInfo {
+    pub kind: PartKind,
+    pub health: i32,
+    pub tiles: Vec<PartTile>,
+    #[serde(default)]
+    pub hitzone: Hitzone,
+    #[serde(default)]
+    pub on_break: Vec<BreakInfo>,
+}
+
+// one tile of a part, relative to the monster's position while facing north
+#[derive(Deserialize)]
+pub struct PartTile {
+    pub x: i32,
+    pub y: i32,
+    pub glyph: String,
+}
+
+#[derive(Deserialize, Copy, Clone)]
+pub enum BreakInfo {
+    LoseAttack(MonsterAttack),
+    WeakenAttack(MonsterAttack),
+    Slow,
+    Flee,
+}
+
+impl MonsterAttack {
+    pub fn to_attack_type(self) -> AttackType {
+        match self {
+            MonsterAttack::Sweep => AttackType::Sweep,
+            MonsterAttack::Punch => AttackType::Punch,
+            MonsterAttack::Stun => AttackType::Stun,
+            MonsterAttack::Push => AttackType::Push,
+            MonsterAttack::Haymaker => AttackType::Haymaker,
+            MonsterAttack::Ranged => AttackType::Ranged,
+        }
+    }
+}
+
+impl BreakInfo {
+    pub fn to_break_effect(self) -> BreakEffect {
+        match self {
+            BreakInfo::LoseAttack(attack) => BreakEffect::LoseAttack(attack.to_attack_type()),
+            BreakInfo::WeakenAttack(attack) => BreakEffect::WeakenAttack(attack.to_attack_type()),
+            BreakInfo::Slow => BreakEffect::Slow,
+            BreakInfo::Flee => BreakEffect::Flee,
+        }
+    }
+}
+
+rltk::embedded_resource!(MONSTER_RAW_DATA, "../../data/monster_info.yaml");
+
+fn load_monster_data() -> MonsterData {
+    rltk::link_resource!(MONSTER_RAW_DATA, "../../data/monster_info.yaml");
+
+    // Retrieve the raw data as an array of u8 (8-bit unsigned chars)
+    let raw_data = rltk::embedding::EMBED
+        .lock()
+        .get_resource("../../data/monster_info.yaml".to_string())
+        .unwrap();
+    let raw_string =
+        std::str::from_utf8(raw_data).expect("Unable to convert to a valid UTF-8 string.");
+
+    serde_yaml::from_str(raw_string).expect("Unable to parse file")
+}
+
+pub fn get_monster_info(name: &str) -> Option<&'static MonsterInfo> {
+    MONSTER_DATA.monsters.iter().find(|info| info.name == name)
+}
+
+pub fn get_monsters() -> &'static [MonsterInfo] {
+    &MONSTER_DATA.monsters
+}
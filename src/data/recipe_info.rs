@@ -0,0 +1,47 @@
+use super::UpgradeCost;
+use serde::Deserialize;
+
+lazy_static! {
+    static ref RECIPE_DATA: RecipeData = load_recipe_data();
+}
+
+#[derive(Deserialize)]
+struct RecipeData {
+    recipes: Vec<RecipeInfo>,
+}
+
+#[derive(Deserialize)]
+pub struct RecipeInfo {
+    // the name of the weapon or armor that gets made
+    pub name: String,
+    pub kind: RecipeKind,
+
+    #[serde(flatten)]
+    pub cost: UpgradeCost,
+}
+
+#[derive(Deserialize, Copy, Clone, PartialEq)]
+pub enum RecipeKind {
+    Weapon,
+    Armor,
+}
+
+rltk::embedded_resource!(RECIPE_RAW_DATA, "../../data/recipe_info.yaml");
+
+fn load_recipe_data() -> RecipeData {
+    rltk::link_resource!(RECIPE_RAW_DATA, "../../data/recipe_info.yaml");
+
+    // Retrieve the raw data as an array of u8 (8-bit unsigned chars)
+    let raw_data = rltk::embedding::EMBED
+        .lock()
+        .get_resource("../../data/recipe_info.yaml".to_string())
+        .unwrap();
+    let raw_string =
+        std::str::from_utf8(raw_data).expect("Unable to convert to a valid UTF-8 string.");
+
+    serde_yaml::from_str(raw_string).expect("Unable to parse file")
+}
+
+pub fn get_recipes() -> &'static Vec<RecipeInfo> {
+    &RECIPE_DATA.recipes
+}
@@ -0,0 +1,56 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+lazy_static! {
+    static ref UPGRADE_DATA: UpgradeData = load_upgrade_data();
+}
+
+#[derive(Deserialize)]
+struct UpgradeData {
+    upgrades: Vec<UpgradeInfo>,
+}
+
+#[derive(Deserialize)]
+pub struct UpgradeInfo {
+    pub name: String,
+    pub kind: UpgradeKind,
+    pub description: String,
+    // one entry for each level of the upgrade
+    pub costs: Vec<UpgradeCost>,
+}
+
+#[derive(Deserialize, Copy, Clone, PartialEq)]
+pub enum UpgradeKind {
+    Sharpness,
+    Handling,
+    Defense,
+    Health,
+}
+
+#[derive(Deserialize)]
+pub struct UpgradeCost {
+    pub money: u32,
+
+    #[serde(default)]
+    pub materials: HashMap<String, u32>,
+}
+
+rltk::embedded_resource!(UPGRADE_RAW_DATA, "../../data/upgrade_info.yaml");
+
+fn load_upgrade_data() -> UpgradeData {
+    rltk::link_resource!(UPGRADE_RAW_DATA, "../../data/upgrade_info.yaml");
+
+    // Retrieve the raw data as an array of u8 (8-bit unsigned chars)
+    let raw_data = rltk::embedding::EMBED
+        .lock()
+        .get_resource("../../data/upgrade_info.yaml".to_string())
+        .unwrap();
+    let raw_string =
+        std::str::from_utf8(raw_data).expect("Unable to convert to a valid UTF-8 string.");
+
+    serde_yaml::from_str(raw_string).expect("Unable to parse file")
+}
+
+pub fn get_upgrades() -> &'static Vec<UpgradeInfo> {
+    &UPGRADE_DATA.upgrades
+}
@@ -0,0 +1,124 @@
+use crate::weapon::Coating;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+lazy_static! {
+    static ref WEAPON_DATA: WeaponData = load_weapon_data();
+}
+
+#[derive(Deserialize)]
+struct WeaponData {
+    weapons: Vec<WeaponInfo>,
+}
+
+#[derive(Deserialize)]
+pub struct WeaponInfo {
+    pub name: String,
+
+    // shorter name for the sidebar
+    pub short_name: Option<String>,
+
+    // whether coatings can be loaded into this weapon
+    #[serde(default)]
+    pub coatings: bool,
+
+    // extra damage dealt by every attack
+    #[serde(default)]
+    pub attack: i32,
+
+    pub sheathed_state: String,
+    pub ready_state: String,
+    pub states: HashMap<String, WeaponState>,
+    pub attacks: HashMap<String, WeaponAttack>,
+}
+
+#[derive(Deserialize)]
+pub struct WeaponState {
+    pub light: Option<WeaponTransition>,
+    pub heavy: Option<WeaponTransition>,
+    pub special: Option<WeaponTransition>,
+
+    // state to enter when guarding, if we can guard from this state
+    pub guard: Option<String>,
+
+    // damage blocked while in this state
+    pub block: Option<u32>,
+
+    // state to enter after a perfect guard
+    pub counter: Option<String>,
+
+    // how far the weapon is charged while held in this state
+    // getting hit while charging drops the weapon back to the ready state
+    pub charge: Option<u8>,
+}
+
+#[derive(Deserialize)]
+pub struct WeaponTransition {
+    pub attack: String,
+    pub next: String,
+}
+
+#[derive(Deserialize)]
+pub struct WeaponAttack {
+    pub name: String,
+    pub stamina: i32,
+
+    // attacks without a type only move the weapon to its next state, like winding up a charge
+    pub attack_type: Option<AttackKind>,
+
+    #[serde(default)]
+    pub origin: AttackOrigin,
+
+    #[serde(default)]
+    pub delay: i32,
+
+    // names to show instead when a coating is loaded
+    #[serde(default)]
+    pub coated_names: HashMap<Coating, String>,
+}
+
+// the AttackType produced by a weapon attack, minus the parts that depend on where the player is
+#[derive(Deserialize, Copy, Clone)]
+pub enum AttackKind {
+    Draw,
+    Thrust { level: u8 },
+    Charge,
+    Sweep,
+    Slash,
+    // reach is how many tiles ahead the swing is centered, 0 swings all around the player
+    ChargedSlash { level: u8, reach: u8 },
+    // arrows use up whatever coating is loaded
+    Shot,
+    PowerShot,
+    Smash { level: u8 },
+    // a blow to the head that does no damage but stuns quickly
+    Stun,
+}
+
+// where the attack is centered, relative to the player
+#[derive(Deserialize, Copy, Clone, Default)]
+pub enum AttackOrigin {
+    #[default]
+    Player,
+    Ahead,
+}
+
+rltk::embedded_resource!(WEAPON_RAW_DATA, "../../data/weapon_info.yaml");
+
+fn load_weapon_data() -> WeaponData {
+    rltk::link_resource!(WEAPON_RAW_DATA, "../../data/weapon_info.yaml");
+
+    // Retrieve the raw data as an array of u8 (8-bit unsigned chars)
+    let raw_data = rltk::embedding::EMBED
+        .lock()
+        .get_resource("../../data/weapon_info.yaml".to_string())
+        .unwrap();
+    let raw_string =
+        std::str::from_utf8(raw_data).expect("Unable to convert to a valid UTF-8 string.");
+
+    serde_yaml::from_str(raw_string).expect("Unable to parse file")
+}
+
+pub fn get_weapon_info(name: &str) -> Option<&'static WeaponInfo> {
+    WEAPON_DATA.weapons.iter().find(|info| info.name == name)
+}
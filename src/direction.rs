@@ -38,6 +38,39 @@ impl Direction {
         }
     }
 
+    // number of clockwise quarter turns to go from this direction to another
+    pub fn turns_to(&self, other: Direction) -> u8 {
+        let mut turns = 0;
+        let mut dir = *self;
+
+        while dir != other {
+            dir = dir.right();
+            turns += 1;
+        }
+
+        turns
+    }
+
+    // rotate an offset defined for something facing north so that it faces this direction instead
+    pub fn rotate_point(&self, point: Point) -> Point {
+        let mut point = point;
+        for _ in 0..Direction::N.turns_to(*self) {
+            point = Point::new(-point.y, point.x);
+        }
+
+        point
+    }
+
+    // undo rotate_point, turning an offset for something facing this direction back to north
+    pub fn unrotate_point(&self, point: Point) -> Point {
+        let mut point = point;
+        for _ in 0..self.turns_to(Direction::N) {
+            point = Point::new(-point.y, point.x);
+        }
+
+        point
+    }
+
     pub fn to_point(&self) -> rltk::Point {
         match self {
             Direction::N => Point::new(0, -1),
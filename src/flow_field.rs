@@ -0,0 +1,214 @@
+use crate::{Map, TileType};
+use rltk::{Algorithm2D, BaseMap, Point};
+use specs::Entity;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+// walking through other creatures is possible once they move, but it's better to go around
+const CREATURE_COST: f32 = 4.0;
+
+// the tiles a creature covers, relative to its position
+pub type Footprint = Vec<(i32, i32)>;
+
+pub fn get_footprint(multi_component: Option<&crate::MultiTile>) -> Footprint {
+    let mut footprint = vec![(0, 0)];
+
+    if let Some(multi) = multi_component {
+        for part in &multi.part_list {
+            for part_pos in part.symbol_map.keys() {
+                footprint.push((part_pos.x, part_pos.y));
+            }
+        }
+    }
+
+    footprint.sort_unstable();
+    footprint.dedup();
+    footprint
+}
+
+// whether a tile is taken by a wall or a creature, not counting the given creature
+fn is_blocked_for(map: &Map, index: usize, ent: Option<Entity>) -> bool {
+    map.blocked_tiles[index] && (ent.is_none() || map.creature_map.get(&index) != ent.as_ref())
+}
+
+// whether a creature with this footprint can stand at a point without overlapping walls or anything else
+pub fn can_stand(map: &Map, ent: Entity, footprint: &Footprint, point: Point) -> bool {
+    let footprint_map = FootprintMap { map, footprint };
+    footprint_map.fits(point.x, point.y)
+        && footprint.iter().all(|(dx, dy)| {
+            !is_blocked_for(map, map.get_index(point.x + dx, point.y + dy), Some(ent))
+        })
+}
+
+// the neighboring tiles a creature with this footprint can step to
+pub fn get_exits(map: &Map, ent: Entity, footprint: &Footprint, index: usize) -> Vec<usize> {
+    let point = map.index_to_point2d(index);
+
+    [(-1, 0), (1, 0), (0, -1), (0, 1)]
+        .iter()
+        .map(|(dx, dy)| Point::new(point.x + dx, point.y + dy))
+        .filter(|next| can_stand(map, ent, footprint, *next))
+        .map(|next| map.point2d_to_index(next))
+        .collect()
+}
+
+// the map as seen by a creature of a particular shape
+struct FootprintMap<'a> {
+    map: &'a Map,
+    footprint: &'a Footprint,
+}
+
+impl<'a> FootprintMap<'a> {
+    fn fits(&self, x: i32, y: i32) -> bool {
+        self.footprint.iter().all(|(dx, dy)| {
+            let (x, y) = (x + dx, y + dy);
+            x >= 1
+                && x < self.map.width
+                && y >= 1
+                && y < self.map.height
+                && self.map.tiles[self.map.get_index(x, y)] != TileType::Wall
+        })
+    }
+
+    fn crowded(&self, x: i32, y: i32) -> bool {
+        self.footprint
+            .iter()
+            .any(|(dx, dy)| is_blocked_for(self.map, self.map.get_index(x + dx, y + dy), None))
+    }
+}
+
+impl<'a> BaseMap for FootprintMap<'a> {
+    fn get_available_exits(&self, idx: usize) -> rltk::SmallVec<[(usize, f32); 10]> {
+        let mut exits = rltk::SmallVec::new();
+        let point = self.map.index_to_point2d(idx);
+
+        for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+            let (x, y) = (point.x + dx, point.y + dy);
+            if self.fits(x, y) {
+                let cost = if self.crowded(x, y) {
+                    1.0 + CREATURE_COST
+                } else {
+                    1.0
+                };
+                exits.push((self.map.get_index(x, y), cost));
+            }
+        }
+
+        exits
+    }
+}
+
+impl<'a> Algorithm2D for FootprintMap<'a> {
+    fn dimensions(&self) -> Point {
+        Point::new(self.map.width, self.map.height)
+    }
+}
+
+// distance maps towards a target, shared by every creature with the same footprint
+// everything is thrown out on update if something on the map moved
+#[derive(Default)]
+pub struct FlowFields {
+    blocked_tiles: Vec<bool>,
+    fields: HashMap<(usize, Footprint), Arc<Vec<f32>>>,
+}
+
+impl FlowFields {
+    // call once before a batch of lookups rather than on every lookup
+    pub fn update(&mut self, map: &Map) {
+        if self.blocked_tiles != map.blocked_tiles {
+            self.fields.clear();
+            self.blocked_tiles = map.blocked_tiles.clone();
+        }
+    }
+
+    pub fn get(&mut self, map: &Map, target: usize, footprint: &Footprint) -> Arc<Vec<f32>> {
+        self.fields
+            .entry((target, footprint.clone()))
+            .or_insert_with(|| {
+                let footprint_map = FootprintMap { map, footprint };
+                let dijkstra_map = rltk::DijkstraMap::new(
+                    map.width as usize,
+                    map.height as usize,
+                    &[target],
+                    &footprint_map,
+                    (map.width * map.height) as f32,
+                );
+
+                Arc::new(dijkstra_map.map)
+            })
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // an open room split by a wall with a one tile gap at (10, 5)
+    fn build_map() -> Map {
+        let mut map = crate::test_util::open_map(20, 12);
+        for y in 1..11 {
+            if y != 5 {
+                let index = map.get_index(10, y);
+                map.tiles[index] = TileType::Wall;
+            }
+        }
+        map.set_blocked_tiles();
+        map
+    }
+
+    #[test]
+    fn fields_are_reused_until_something_moves() {
+        let mut map = build_map();
+        let mut flow = FlowFields::default();
+        let target = map.get_index(15, 5);
+        let footprint = get_footprint(None);
+        flow.update(&map);
+
+        let first = flow.get(&map, target, &footprint);
+        let second = flow.get(&map, target, &footprint);
+        assert!(Arc::ptr_eq(&first, &second));
+
+        let index = map.get_index(3, 3);
+        map.blocked_tiles[index] = true;
+        let stale = flow.get(&map, target, &footprint);
+        assert!(Arc::ptr_eq(&first, &stale));
+
+        flow.update(&map);
+        let third = flow.get(&map, target, &footprint);
+        assert!(!Arc::ptr_eq(&first, &third));
+    }
+
+    #[test]
+    fn large_creatures_do_not_fit_through_gaps() {
+        let map = build_map();
+        let mut flow = FlowFields::default();
+        flow.update(&map);
+        let target = map.get_index(15, 5);
+        let start = map.get_index(5, 5);
+
+        let small = flow.get(&map, target, &get_footprint(None));
+        assert_eq!(small[start], 10.0);
+
+        let wide = vec![(0, -1), (0, 0), (0, 1)];
+        let large = flow.get(&map, target, &wide);
+        assert_eq!(large[start], f32::MAX);
+    }
+
+    #[test]
+    fn creatures_are_only_blocked_by_others() {
+        use specs::prelude::*;
+
+        let mut world = World::new();
+        let (ent, other) = (world.create_entity().build(), world.create_entity().build());
+        let mut map = build_map();
+        let wide = vec![(-1, 0), (0, 0), (1, 0)];
+        let (here, index) = (Point::new(4, 4), map.get_index(4, 4));
+        map.track_creature(ent, index, None);
+
+        assert!(can_stand(&map, ent, &wide, here));
+        assert!(!can_stand(&map, other, &wide, Point::new(5, 4)));
+        assert!(!can_stand(&map, ent, &wide, Point::new(9, 4)));
+        assert_eq!(get_exits(&map, ent, &wide, index).len(), 4);
+    }
+}
@@ -242,6 +242,9 @@ pub fn draw_viewable_info(ecs: &World, ctx: &mut Rltk, entity: &Entity, index: u
     let healths = ecs.read_storage::<Health>();
     let atk_in_progress = ecs.read_storage::<AttackInProgress>();
     let blocking = ecs.read_storage::<BlockAttack>();
+    let stun_meters = ecs.read_storage::<StunMeter>();
+    let statuses = ecs.read_storage::<StatusEffects>();
+    let multis = ecs.read_storage::<MultiTile>();
     let map = ecs.fetch::<Map>();
 
     let pos = positions
@@ -260,7 +263,23 @@ pub fn draw_viewable_info(ecs: &World, ctx: &mut Rltk, entity: &Entity, index: u
         selected_color,
     );
 
-    let (box_x, box_y) = position_box(ctx, x, y, 15, 10, selected_color, bg_color);
+    // only parts the player has hit get a line of hitzone info
+    let discovered_parts = multis
+        .get(*entity)
+        .map(|multi| {
+            multi
+                .part_list
+                .iter()
+                .filter(|part| !part.discovered.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    let box_h = std::cmp::max(
+        10,
+        6 + (view.description.len() + discovered_parts.len()) as i32,
+    );
+    let (box_x, box_y) = position_box(ctx, x, y, 20, box_h, selected_color, bg_color);
 
     ctx.print(box_x + 1, box_y, view.name.clone());
     ctx.print(
@@ -269,7 +288,26 @@ pub fn draw_viewable_info(ecs: &World, ctx: &mut Rltk, entity: &Entity, index: u
         format!("HP: {}/{}", health.current, health.max),
     );
 
-    if atk_in_progress.get(*entity).is_some() {
+    if let Some(meter) = stun_meters.get(*entity) {
+        ctx.print(
+            box_x + 1,
+            box_y + 2,
+            format!("Stun: {}/{}", meter.current, meter.threshold),
+        );
+    }
+
+    let status = statuses.get(*entity);
+    let disabling_status = status.and_then(|status| {
+        status
+            .effects
+            .iter()
+            .find(|effect| effect.kind.skips_turn())
+            .map(|effect| effect.kind)
+    });
+
+    if let Some(kind) = disabling_status {
+        ctx.print(box_x + 1, box_y + 3, kind.name());
+    } else if atk_in_progress.get(*entity).is_some() {
         ctx.print(box_x + 1, box_y + 3, "Attacking");
     } else if blocking.get(*entity).is_some() {
         ctx.print(box_x + 1, box_y + 3, "Blocking");
@@ -277,9 +315,41 @@ pub fn draw_viewable_info(ecs: &World, ctx: &mut Rltk, entity: &Entity, index: u
         ctx.print(box_x + 1, box_y + 3, "Idle");
     }
 
+    if let Some(status) = status {
+        super::sidebar::draw_status_icons(ctx, box_x + 1, box_y + 4, status);
+    }
+
     for (i, line) in view.description.iter().enumerate() {
         ctx.print(box_x + 1, box_y + 5 + i as i32, line.clone());
     }
+
+    let hitzone_y = box_y + 5 + view.description.len() as i32;
+    for (i, part) in discovered_parts.iter().enumerate() {
+        draw_hitzone_info(ctx, box_x + 1, hitzone_y + i as i32, part);
+    }
+}
+
+// show the part's symbol followed by the multiplier for each damage kind tried on it
+fn draw_hitzone_info(ctx: &mut Rltk, x: i32, y: i32, part: &MonsterPart) {
+    if let Some(symbol) = part.symbol_map.values().next() {
+        ctx.set(x, y, text_color(), bg_color(), *symbol);
+    }
+
+    let mut x = x + 2;
+    for kind in part.discovered.iter() {
+        let mult = part.hitzone.multiplier(*kind);
+        let color = if mult >= WEAK_POINT_MULT {
+            text_success_color()
+        } else if mult <= DEFLECT_MULT {
+            text_failed_color()
+        } else {
+            text_color()
+        };
+
+        let text = format!("{}{}", kind.abbreviation(), mult);
+        ctx.print_color(x, y, color, bg_color(), &text);
+        x += text.len() as i32 + 1;
+    }
 }
 
 // draw a box stemming from a given point
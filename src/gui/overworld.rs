@@ -128,7 +128,11 @@ pub fn draw_missions(
     }
 }
 
-pub fn draw_upgrades(ctx: &mut Rltk) {
+pub fn draw_equipment(
+    ctx: &mut Rltk,
+    inventory: &crate::inventory::Inventory,
+    selected_idx: usize,
+) {
     let book_x = 8;
     let book_y = 6;
     let box_w = 40;
@@ -143,11 +147,107 @@ pub fn draw_upgrades(ctx: &mut Rltk) {
         RGB::named(rltk::BLACK),
     );
 
-    let header = "Placeholder";
+    let header = "Equipment Box";
     ctx.print(book_x + (box_w - header.len()) / 2, book_y + 2, header);
+
+    ctx.print(
+        book_x + 1,
+        book_y + 4,
+        format!("Weapon: {}", inventory.weapon.name()),
+    );
+
+    for (i, slot) in crate::data::ALL_SLOTS.iter().enumerate() {
+        let piece_name = inventory
+            .armor
+            .iter()
+            .find(|piece| piece.slot == *slot)
+            .map_or("-".to_string(), |piece| piece.name.clone());
+        ctx.print(
+            book_x + 1,
+            book_y + 5 + i,
+            format!("{:?}: {}", slot, piece_name),
+        );
+    }
+
+    // active skills go beside the worn armor
+    let skills_x = book_x + 25;
+    ctx.print(skills_x, book_y + 4, "Skills:");
+    let mut skill_row = book_y + 5;
+    for skill in crate::data::ALL_SKILLS.iter() {
+        let level = inventory.skill_level(*skill);
+        if level > 0 {
+            ctx.print(skills_x, skill_row, format!("{} {}", skill.name(), level));
+            skill_row += 1;
+        }
+    }
+
+    if skill_row == book_y + 5 {
+        ctx.print(skills_x, skill_row, "None");
+    }
+
+    // stored weapons are listed first, then stored armor
+    let stored_names = inventory
+        .weapon_box
+        .iter()
+        .map(|weapon| weapon.name())
+        .chain(inventory.armor_box.iter().map(|piece| piece.name.clone()));
+    let list_y = book_y + 11;
+    let mut list_len = 0;
+
+    for (i, name) in stored_names.enumerate() {
+        let row = list_y + 2 * i;
+        let mut text_color = crate::text_color();
+
+        if i == selected_idx {
+            ctx.set_active_console(0);
+            for dx in 0..box_w - 1 {
+                ctx.set_bg(book_x + 1 + dx, row, crate::select_highlight_color());
+            }
+            ctx.set_active_console(1);
+
+            text_color = crate::select_text_color();
+        }
+
+        ctx.print_color(book_x + 1, row, text_color, crate::bg_color(), name);
+        list_len += 1;
+    }
+
+    // materials are kept in the box as well
+    let materials_y = list_y + 2 * list_len;
+    ctx.print(book_x + 1, materials_y, "Materials:");
+
+    let mut materials: Vec<_> = inventory.materials.iter().collect();
+    materials.sort();
+
+    if materials.is_empty() {
+        ctx.print(book_x + 2, materials_y + 2, "None");
+    }
+
+    for (i, (name, count)) in materials.iter().enumerate() {
+        ctx.print(
+            book_x + 2,
+            materials_y + 2 + i,
+            format!("{} x{}", name, count),
+        );
+    }
+
+    // controls along the bottom of the box
+    let hint_y = book_y + box_h - 1;
+    let icon_color = crate::text_highlight_color();
+    let bg_color = crate::bg_color();
+
+    ctx.set(book_x + 1, hint_y, icon_color, bg_color, 25);
+    ctx.set(book_x + 2, hint_y, icon_color, bg_color, 24);
+    ctx.print(book_x + 4, hint_y, "select");
+
+    ctx.print_color(book_x + 12, hint_y, icon_color, bg_color, "[SPACE]");
+    ctx.print(book_x + 20, hint_y, "equip");
+
+    ctx.print_color(book_x + 27, hint_y, icon_color, bg_color, "[ESC]");
+    ctx.print(book_x + 33, hint_y, "close");
 }
 
-pub fn draw_shop(ctx: &mut Rltk) {
+pub fn draw_upgrades(ctx: &mut Rltk, inventory: &crate::inventory::Inventory, selected_idx: usize) {
     let book_x = 8;
     let book_y = 6;
     let box_w = 40;
@@ -162,6 +262,258 @@ pub fn draw_shop(ctx: &mut Rltk) {
         RGB::named(rltk::BLACK),
     );
 
-    let header = "Placeholder";
+    let header = "Upgrades";
     ctx.print(book_x + (box_w - header.len()) / 2, book_y + 2, header);
+
+    let money_str = format!("{}z", inventory.money);
+    ctx.print(book_x + box_w - money_str.len(), book_y + 2, money_str);
+
+    let upgrades = crate::data::get_upgrades();
+    for (i, upgrade) in upgrades.iter().enumerate() {
+        let row = book_y + 4 + 2 * i;
+        let mut text_color = crate::text_color();
+
+        if i == selected_idx {
+            ctx.set_active_console(0);
+            for dx in 0..box_w - 1 {
+                ctx.set_bg(book_x + 1 + dx, row, crate::select_highlight_color());
+            }
+            ctx.set_active_console(1);
+
+            text_color = crate::select_text_color();
+        }
+
+        let level_str = format!(
+            "Lv {}/{}",
+            inventory.upgrade_level(upgrade.kind),
+            upgrade.costs.len()
+        );
+        ctx.print_color(
+            book_x + box_w - level_str.len(),
+            row,
+            text_color,
+            crate::bg_color(),
+            level_str,
+        );
+        ctx.print_color(
+            book_x + 1,
+            row,
+            text_color,
+            crate::bg_color(),
+            upgrade.name.clone(),
+        );
+    }
+
+    if let Some(upgrade) = upgrades.get(selected_idx) {
+        let info_y = book_y + box_h - 12;
+        ctx.print(book_x + 1, info_y, upgrade.description.clone());
+
+        match upgrade.costs.get(inventory.upgrade_level(upgrade.kind)) {
+            None => {
+                ctx.print_color(
+                    book_x + 1,
+                    info_y + 2,
+                    crate::text_success_color(),
+                    crate::bg_color(),
+                    "Fully upgraded!",
+                );
+            }
+            Some(cost) => draw_cost(ctx, inventory, cost, book_x + 1, info_y + 2),
+        }
+    }
+
+    ctx.print(book_x + 1, book_y + box_h - 2, "[TAB] craft");
+}
+
+pub fn draw_crafting(ctx: &mut Rltk, inventory: &crate::inventory::Inventory, selected_idx: usize) {
+    let book_x = 8;
+    let book_y = 6;
+    let box_w = 40;
+    let box_h = 40;
+
+    ctx.draw_box(
+        book_x,
+        book_y,
+        box_w,
+        box_h,
+        RGB::named(rltk::GREY),
+        RGB::named(rltk::BLACK),
+    );
+
+    let header = "Crafting";
+    ctx.print(book_x + (box_w - header.len()) / 2, book_y + 2, header);
+
+    let money_str = format!("{}z", inventory.money);
+    ctx.print(book_x + box_w - money_str.len(), book_y + 2, money_str);
+
+    let recipes = crate::data::get_recipes();
+    for (i, recipe) in recipes.iter().enumerate() {
+        let row = book_y + 4 + 2 * i;
+        let mut text_color = crate::text_color();
+
+        if i == selected_idx {
+            ctx.set_active_console(0);
+            for dx in 0..box_w - 1 {
+                ctx.set_bg(book_x + 1 + dx, row, crate::select_highlight_color());
+            }
+            ctx.set_active_console(1);
+
+            text_color = crate::select_text_color();
+        }
+
+        let (status_str, status_color) = if inventory.owns(recipe) {
+            ("Owned", crate::text_inactive_color())
+        } else if inventory.can_afford(&recipe.cost) {
+            ("Craftable", crate::text_success_color())
+        } else {
+            ("Missing", crate::text_failed_color())
+        };
+        ctx.print_color(
+            book_x + box_w - status_str.len(),
+            row,
+            status_color,
+            crate::bg_color(),
+            status_str,
+        );
+        ctx.print_color(
+            book_x + 1,
+            row,
+            text_color,
+            crate::bg_color(),
+            recipe.name.clone(),
+        );
+    }
+
+    if let Some(recipe) = recipes.get(selected_idx) {
+        let info_y = book_y + box_h - 12;
+        let kind_str = match recipe.kind {
+            crate::data::RecipeKind::Weapon => "Weapon".to_string(),
+            crate::data::RecipeKind::Armor => {
+                let piece = crate::data::get_armor_info(&recipe.name).expect("Unknown armor");
+                format!("{:?} armor, +{} defense", piece.slot, piece.defense)
+            }
+        };
+        ctx.print(book_x + 1, info_y, kind_str);
+        draw_cost(ctx, inventory, &recipe.cost, book_x + 1, info_y + 2);
+    }
+
+    ctx.print(book_x + 1, book_y + box_h - 2, "[TAB] upgrade");
+}
+
+// money on the first line, then each material with how many are owned
+fn draw_cost(
+    ctx: &mut Rltk,
+    inventory: &crate::inventory::Inventory,
+    cost: &crate::data::UpgradeCost,
+    x: usize,
+    y: usize,
+) {
+    let money_color = if inventory.money >= cost.money {
+        crate::text_color()
+    } else {
+        crate::text_failed_color()
+    };
+    ctx.print_color(
+        x,
+        y,
+        money_color,
+        crate::bg_color(),
+        format!("Cost: {}z", cost.money),
+    );
+
+    for (i, (name, amount)) in cost.materials.iter().enumerate() {
+        let owned = inventory.material_count(name);
+        let material_color = if owned >= *amount {
+            crate::text_color()
+        } else {
+            crate::text_failed_color()
+        };
+        ctx.print_color(
+            x + 1,
+            y + 2 + 2 * i,
+            material_color,
+            crate::bg_color(),
+            format!("{} {}/{}", name, owned, amount),
+        );
+    }
+}
+
+pub fn draw_shop(
+    ctx: &mut Rltk,
+    inventory: &crate::inventory::Inventory,
+    selected_idx: usize,
+    selling: bool,
+) {
+    let book_x = 8;
+    let book_y = 6;
+    let box_w = 40;
+    let box_h = 40;
+
+    ctx.draw_box(
+        book_x,
+        book_y,
+        box_w,
+        box_h,
+        RGB::named(rltk::GREY),
+        RGB::named(rltk::BLACK),
+    );
+
+    let header = if selling { "Sell Items" } else { "Buy Items" };
+    ctx.print(book_x + (box_w - header.len()) / 2, book_y + 2, header);
+
+    let money_str = format!("{}z", inventory.money);
+    ctx.print(book_x + box_w - money_str.len(), book_y + 2, money_str);
+
+    let items = if selling {
+        inventory.owned_items()
+    } else {
+        crate::consumable::SHOP_STOCK.to_vec()
+    };
+
+    if items.is_empty() {
+        ctx.print(book_x + 1, book_y + 4, "Nothing to sell");
+    }
+
+    for (i, item) in items.iter().enumerate() {
+        let row = book_y + 4 + 2 * i;
+        let mut text_color = crate::text_color();
+
+        if i == selected_idx {
+            ctx.set_active_console(0);
+            for dx in 0..box_w - 1 {
+                ctx.set_bg(book_x + 1 + dx, row, crate::select_highlight_color());
+            }
+            ctx.set_active_console(1);
+
+            text_color = crate::select_text_color();
+        }
+
+        let price = if selling {
+            item.sell_price()
+        } else {
+            item.price()
+        };
+        let price_str = format!("{}z", price);
+        ctx.print_color(
+            book_x + box_w - price_str.len(),
+            row,
+            text_color,
+            crate::bg_color(),
+            price_str,
+        );
+        ctx.print_color(
+            book_x + 1,
+            row,
+            text_color,
+            crate::bg_color(),
+            format!("{} (x{})", item.name(), inventory.item_count(*item)),
+        );
+    }
+
+    if let Some(item) = items.get(selected_idx) {
+        ctx.print(book_x + 1, book_y + box_h - 4, item.description());
+    }
+
+    let tab_str = if selling { "[TAB] buy" } else { "[TAB] sell" };
+    ctx.print(book_x + 1, book_y + box_h - 2, tab_str);
 }
@@ -8,6 +8,8 @@ pub fn draw_sidebar(gs: &State, ctx: &mut Rltk) {
     let healths = gs.ecs.read_storage::<Health>();
     let stams = gs.ecs.read_storage::<Stamina>();
     let views = gs.ecs.read_storage::<Viewable>();
+    let positions = gs.ecs.read_storage::<Position>();
+    let ai_states = gs.ecs.read_storage::<AiState>();
 
     let player = gs.ecs.fetch::<Entity>();
     let m_info = gs.ecs.fetch::<MissionInfo>();
@@ -51,6 +53,10 @@ pub fn draw_sidebar(gs: &State, ctx: &mut Rltk) {
         ctx.print(x, y + 2, "Charging!");
     }
 
+    if let Some(status) = gs.ecs.read_storage::<StatusEffects>().get(*player) {
+        draw_status_icons(ctx, x, y + 3, status);
+    }
+
     // Quest info
     y += 4;
     ctx.print(x, y, "Quest:");
@@ -90,9 +96,40 @@ pub fn draw_sidebar(gs: &State, ctx: &mut Rltk) {
                             "In prgrss",
                         );
                         ctx.print(x, y + 2, "Remaining");
+                        let player_point = positions.get(*player).map(|pos| pos.as_point());
+                        let map = gs.ecs.fetch::<Map>();
+
                         for (i, ent) in m_info.remaining.iter().enumerate() {
+                            let row = y + 4 + 2 * i as i32;
                             if let Some(ent_view) = views.get(*ent) {
-                                ctx.print(x + 2, y + 4 + 2 * i as i32, ent_view.name.clone());
+                                ctx.print(x + 2, row, ent_view.name.clone());
+                            }
+
+                            let fleeing = ai_states
+                                .get(*ent)
+                                .is_some_and(|state| matches!(state.status, Behavior::Flee));
+                            let in_view = positions
+                                .get(*ent)
+                                .is_some_and(|pos| map.visible_tiles[map.get_index(pos.x, pos.y)]);
+
+                            if fleeing {
+                                ctx.print_color(
+                                    x + 3,
+                                    row + 1,
+                                    text_highlight_color(),
+                                    bg_color(),
+                                    "is fleeing",
+                                );
+                            } else if let (false, Some(seen), Some(player_point)) =
+                                (in_view, m_info.last_seen.get(ent), player_point)
+                            {
+                                ctx.print_color(
+                                    x + 3,
+                                    row + 1,
+                                    text_inactive_color(),
+                                    bg_color(),
+                                    format!("seen {}", describe_offset(player_point, *seen)),
+                                );
                             }
                         }
                     }
@@ -122,21 +159,34 @@ pub fn draw_sidebar(gs: &State, ctx: &mut Rltk) {
     }
 
     // Resources
-    y = 30;
+    y = 24;
     ctx.print(x, y, format!("Money:{}z", gs.player_inventory.money));
-    ctx.print(
-        x,
-        y + 2,
-        format!("Weapon:{}", gs.player_inventory.weapon.name()),
-    );
+    ctx.print(x, y + 2, "Weapon:");
+    ctx.print(x + 1, y + 3, gs.player_inventory.weapon.name());
     ctx.print(
         x,
         y + 4,
-        format!("Armor:+{}", gs.player_inventory.armor_level),
+        format!("Armor:+{}", gs.player_inventory.defense()),
     );
 
+    match gs.player_inventory.selected_item() {
+        None => {
+            ctx.print_color(x, y + 6, text_inactive_color(), bg_color(), "q No items");
+        }
+        Some(item) => {
+            ctx.print_color(x, y + 6, text_highlight_color(), bg_color(), "q");
+            ctx.print(x + 2, y + 6, item.name());
+            ctx.print_color(x, y + 7, text_highlight_color(), bg_color(), "e");
+            ctx.print(
+                x + 2,
+                y + 7,
+                format!("Next  x{}", gs.player_inventory.item_count(item)),
+            );
+        }
+    }
+
     // Weapon info
-    y = 38;
+    y = 36;
     ctx.print(x, y, "Controls");
     draw_movement_controls(ctx, x, y + 2, text_highlight_color(), bg_color(), false);
 
@@ -154,6 +204,39 @@ pub fn draw_sidebar(gs: &State, ctx: &mut Rltk) {
     super::tooltip::draw_tooltips(&gs.ecs, ctx);
 }
 
+// distance and compass direction from one point to another, like "12 NE"
+fn describe_offset(from: rltk::Point, to: rltk::Point) -> String {
+    let dx = to.x - from.x;
+    let dy = to.y - from.y;
+    let distance = std::cmp::max(dx.abs(), dy.abs());
+
+    // only count an axis if it's a meaningful part of the offset
+    let vertical = if dy * 2 < -dx.abs() {
+        "N"
+    } else if dy * 2 > dx.abs() {
+        "S"
+    } else {
+        ""
+    };
+    let horizontal = if dx * 2 > dy.abs() {
+        "E"
+    } else if dx * 2 < -dy.abs() {
+        "W"
+    } else {
+        ""
+    };
+
+    format!("{} {}{}", distance, vertical, horizontal)
+}
+
+// one colored icon per active status effect
+pub fn draw_status_icons(ctx: &mut Rltk, x: i32, y: i32, status: &StatusEffects) {
+    for (i, effect) in status.effects.iter().enumerate() {
+        let (symbol, color) = effect.kind.icon();
+        ctx.set(x + i as i32, y, color, bg_color(), symbol);
+    }
+}
+
 fn draw_resource_bar(
     ctx: &mut Rltk,
     curr: i32,
@@ -209,6 +292,12 @@ fn add_weapon_text(
         ctx.print_color(x, y, sheathe_icon_color, bg_color, "s");
         ctx.print(x + 2, y, "Sheathe");
 
+        if weapon.can_guard() {
+            y += 2;
+            ctx.print_color(x, y, sheathe_icon_color, bg_color, "g");
+            ctx.print(x + 2, y, "Guard");
+        }
+
         if let Some(stam_cost) = weapon.can_activate_cost(WeaponButton::Light) {
             let attack_icon_color = if stam_cost <= player_stam {
                 icon_color
@@ -249,4 +338,18 @@ fn add_weapon_text(
             ctx.print(x + 2, y, &name);
         }
     }
+
+    if let Some(level) = weapon.charge_level() {
+        y += 2;
+        ctx.print(x, y, "Charge");
+        for i in 0..level {
+            ctx.set(
+                x + 7 + i as i32,
+                y,
+                icon_color,
+                bg_color,
+                rltk::to_cp437('*'),
+            );
+        }
+    }
 }
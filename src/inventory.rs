@@ -1,17 +1,192 @@
+use crate::consumable::Consumable;
+use crate::data::{ArmorInfo, RecipeInfo, RecipeKind, Skill, UpgradeCost, UpgradeKind};
+use std::collections::HashMap;
+
+pub const MAX_SKILL_LEVEL: u32 = 3;
+
 pub struct Inventory {
     pub money: u32,
     pub weapon: Box<dyn crate::weapon::Weapon>,
+    // owned weapons that aren't currently equipped
+    pub weapon_box: Vec<Box<dyn crate::weapon::Weapon>>,
     pub armor_level: u32,
-    pub consumables: Vec<String>,
+    // one piece of armor per slot
+    pub armor: Vec<&'static ArmorInfo>,
+    pub armor_box: Vec<&'static ArmorInfo>,
+    pub health_level: u32,
+    pub materials: HashMap<String, u32>,
+    pub consumables: Vec<Consumable>,
+    // which of the owned items the quick use key uses
+    pub selected_item: usize,
 }
 
 impl Inventory {
     pub fn new() -> Self {
         Self {
             money: 0,
-            weapon: Box::new(crate::weapon::lance::Lance::new()),
+            weapon: crate::weapon::build_weapon("Lance"),
+            weapon_box: vec![
+                crate::weapon::build_weapon("Great Sword"),
+                crate::weapon::build_weapon("Hammer"),
+                crate::weapon::build_weapon("Bow"),
+            ],
             armor_level: 0,
-            consumables: vec![],
+            armor: vec![],
+            armor_box: vec![],
+            health_level: 0,
+            materials: HashMap::new(),
+            consumables: vec![Consumable::Potion, Consumable::Potion],
+            selected_item: 0,
+        }
+    }
+
+    // swap the equipped weapon with one from the box
+    pub fn equip_weapon(&mut self, index: usize) {
+        if let Some(stored) = self.weapon_box.get_mut(index) {
+            std::mem::swap(&mut self.weapon, stored);
+            stored.sheathe();
+            self.weapon.sheathe();
+        }
+    }
+
+    // wear a piece of armor, putting away whatever was in its slot
+    pub fn equip_armor(&mut self, piece: &'static ArmorInfo) {
+        if let Some(index) = self.armor.iter().position(|worn| worn.slot == piece.slot) {
+            let removed = self.armor.remove(index);
+            self.armor_box.push(removed);
+        }
+
+        self.armor_box.retain(|stored| stored.name != piece.name);
+        self.armor.push(piece);
+    }
+
+    pub fn defense(&self) -> i32 {
+        self.armor_level as i32 + self.armor.iter().map(|piece| piece.defense).sum::<i32>()
+    }
+
+    // skill points from all worn armor, up to the max level
+    pub fn skill_level(&self, skill: Skill) -> u32 {
+        let points = self
+            .armor
+            .iter()
+            .filter_map(|piece| piece.skills.get(&skill))
+            .sum::<u32>();
+
+        std::cmp::min(points, MAX_SKILL_LEVEL)
+    }
+
+    // equip an armor piece from the box
+    pub fn equip_stored_armor(&mut self, index: usize) {
+        if let Some(piece) = self.armor_box.get(index).copied() {
+            self.equip_armor(piece);
+        }
+    }
+
+    pub fn owns(&self, recipe: &RecipeInfo) -> bool {
+        match recipe.kind {
+            RecipeKind::Weapon => {
+                self.weapon.base_name() == recipe.name
+                    || self
+                        .weapon_box
+                        .iter()
+                        .any(|weapon| weapon.base_name() == recipe.name)
+            }
+            RecipeKind::Armor => self
+                .armor
+                .iter()
+                .chain(self.armor_box.iter())
+                .any(|piece| piece.name == recipe.name),
+        }
+    }
+
+    pub fn craft(&mut self, recipe: &RecipeInfo) {
+        self.pay(&recipe.cost);
+
+        match recipe.kind {
+            RecipeKind::Weapon => self
+                .weapon_box
+                .push(crate::weapon::build_weapon(&recipe.name)),
+            RecipeKind::Armor => {
+                let piece = crate::data::get_armor_info(&recipe.name).expect("Unknown armor");
+                self.equip_armor(piece);
+            }
+        }
+    }
+
+    // each type of item owned, in shop order
+    pub fn owned_items(&self) -> Vec<Consumable> {
+        crate::consumable::SHOP_STOCK
+            .iter()
+            .filter(|item| self.consumables.contains(item))
+            .copied()
+            .collect()
+    }
+
+    pub fn item_count(&self, item: Consumable) -> usize {
+        self.consumables
+            .iter()
+            .filter(|owned| **owned == item)
+            .count()
+    }
+
+    pub fn selected_item(&self) -> Option<Consumable> {
+        let owned = self.owned_items();
+        if owned.is_empty() {
+            None
+        } else {
+            Some(owned[self.selected_item % owned.len()])
+        }
+    }
+
+    pub fn remove_item(&mut self, item: Consumable) -> bool {
+        match self.consumables.iter().position(|owned| *owned == item) {
+            None => false,
+            Some(index) => {
+                self.consumables.remove(index);
+                true
+            }
+        }
+    }
+
+    pub fn material_count(&self, name: &str) -> u32 {
+        self.materials.get(name).copied().unwrap_or(0)
+    }
+
+    pub fn can_afford(&self, cost: &UpgradeCost) -> bool {
+        self.money >= cost.money
+            && cost
+                .materials
+                .iter()
+                .all(|(name, amount)| self.material_count(name) >= *amount)
+    }
+
+    pub fn pay(&mut self, cost: &UpgradeCost) {
+        self.money -= cost.money;
+
+        for (name, amount) in &cost.materials {
+            if let Some(count) = self.materials.get_mut(name) {
+                *count -= amount;
+            }
+        }
+
+        self.materials.retain(|_, count| *count > 0);
+    }
+
+    pub fn upgrade_level(&self, kind: UpgradeKind) -> usize {
+        match kind {
+            UpgradeKind::Sharpness => self.weapon.upgrades().sharpness as usize,
+            UpgradeKind::Handling => self.weapon.upgrades().handling as usize,
+            UpgradeKind::Defense => self.armor_level as usize,
+            UpgradeKind::Health => self.health_level as usize,
+        }
+    }
+
+    pub fn apply_upgrade(&mut self, kind: UpgradeKind) {
+        match kind {
+            UpgradeKind::Sharpness => self.weapon.upgrades_mut().sharpness += 1,
+            UpgradeKind::Handling => self.weapon.upgrades_mut().handling += 1,
+            UpgradeKind::Defense => self.armor_level += 1,
+            UpgradeKind::Health => self.health_level += 1,
         }
     }
 }
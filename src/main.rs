@@ -9,11 +9,15 @@ use rltk::{GameState, Rltk, RGB};
 use specs::prelude::*;
 
 mod attack_type;
+#[cfg(feature = "bench")]
+mod bench;
 mod camera;
 mod colors;
 mod components;
+mod consumable;
 mod data;
 mod direction;
+mod flow_field;
 mod gamelog;
 mod gui;
 mod inventory;
@@ -25,8 +29,10 @@ mod player;
 mod quest;
 mod range_type;
 mod spawn;
+mod status;
 mod sys_ai;
 mod sys_attack;
+mod sys_awareness;
 mod sys_death;
 mod sys_mapindex;
 mod sys_movement;
@@ -34,9 +40,12 @@ mod sys_partbreak;
 mod sys_particle;
 mod sys_partmove;
 mod sys_pickup;
+mod sys_projectile;
 mod sys_push;
 mod sys_turn;
 mod sys_visibility;
+#[cfg(test)]
+mod test_util;
 mod weapon;
 
 pub use attack_type::*;
@@ -49,6 +58,7 @@ pub use mission_info::MissionInfo;
 pub use monster_part::*;
 pub use range_type::*;
 pub use spawn::info::SpawnInfo;
+pub use status::{StatusEffects, StatusKind};
 pub use sys_ai::{Behavior, NextIntent};
 pub use sys_particle::{ParticleBuilder, ParticleRequest};
 
@@ -81,8 +91,17 @@ pub enum RunState {
     MissionSelect {
         index: usize,
     },
-    Shop,
-    Blacksmith,
+    Shop {
+        index: usize,
+        selling: bool,
+    },
+    Blacksmith {
+        index: usize,
+        crafting: bool,
+    },
+    EquipmentSelect {
+        index: usize,
+    },
     Dead {
         success: bool,
     },
@@ -127,10 +146,12 @@ impl State {
         self.ecs.register::<Moveset>();
 
         self.ecs.register::<AttackInProgress>();
+        self.ecs.register::<HitFlag>();
         self.ecs.register::<BlockAttack>();
         self.ecs.register::<AiState>();
         self.ecs.register::<Heal>();
         self.ecs.register::<Item>();
+        self.ecs.register::<Carveable>();
         self.ecs.register::<Openable>();
 
         self.ecs.register::<MultiTile>();
@@ -138,8 +159,15 @@ impl State {
 
         self.ecs.register::<PushForce>();
         self.ecs.register::<Npc>();
-        self.ecs.register::<Invulnerable>();
         self.ecs.register::<MissionTarget>();
+        self.ecs.register::<Nest>();
+        self.ecs.register::<Awareness>();
+        self.ecs.register::<StuckInfo>();
+        self.ecs.register::<PackMember>();
+        self.ecs.register::<Projectile>();
+        self.ecs.register::<StunMeter>();
+        self.ecs.register::<StatBonus>();
+        self.ecs.register::<StatusEffects>();
     }
 
     fn new_game(&mut self) {
@@ -147,6 +175,8 @@ impl State {
 
         self.ecs.insert(RunState::Running);
         self.ecs.insert(sys_particle::ParticleBuilder::new());
+        self.ecs.insert(sys_awareness::NoiseQueue::default());
+        self.ecs.insert(flow_field::FlowFields::default());
 
         let mut rng = rltk::RandomNumberGenerator::new();
 
@@ -178,10 +208,13 @@ impl State {
     fn run_systems(&mut self) -> RunState {
         self.tick += 1;
 
+        // monsters react to what the player did last tick before deciding what to do
+        sys_awareness::AwarenessSystem.run_now(&self.ecs);
         sys_ai::AiSystem.run_now(&self.ecs);
         sys_turn::TurnSystem.run_now(&self.ecs);
 
         sys_movement::MovementSystem.run_now(&self.ecs);
+        sys_projectile::ProjectileSystem.run_now(&self.ecs);
         sys_attack::AttackSystem.run_now(&self.ecs);
 
         // ensure indexes are correct before handling part movements
@@ -459,6 +492,7 @@ impl GameState for State {
                     next_status = RunState::AwaitingInput;
                 }
                 Some(mut quest) => {
+                    player::update_stat_bonus(self);
                     self.new_level(&quest.map_builder_args, &quest.spawn_info);
                     sys_visibility::VisibilitySystem.run_now(&self.ecs);
 
@@ -494,9 +528,13 @@ impl GameState for State {
                                 );
                             }
 
-                            // clear out temp mission info
+                            // clear out temp mission info, keeping anything we carved
                             {
                                 let mut m_info = self.ecs.fetch_mut::<MissionInfo>();
+                                for (name, count) in m_info.materials.drain() {
+                                    *self.player_inventory.materials.entry(name).or_insert(0) +=
+                                        count;
+                                }
                                 m_info.reset();
                             }
                             self.selected_quest = None;
@@ -512,27 +550,24 @@ impl GameState for State {
 
                 next_status = player::mission_select_input(self, ctx, index);
             }
-            RunState::Shop => {
-                gui::overworld::draw_shop(ctx);
-                match ctx.key {
-                    None => {}
-                    Some(key) => {
-                        if key == rltk::VirtualKeyCode::Escape {
-                            next_status = RunState::Running;
-                        }
-                    }
-                }
+            RunState::Shop { index, selling } => {
+                gui::overworld::draw_shop(ctx, &self.player_inventory, index, selling);
+
+                next_status = player::shop_input(self, ctx, index, selling);
             }
-            RunState::Blacksmith => {
-                gui::overworld::draw_upgrades(ctx);
-                match ctx.key {
-                    None => {}
-                    Some(key) => {
-                        if key == rltk::VirtualKeyCode::Escape {
-                            next_status = RunState::Running;
-                        }
-                    }
+            RunState::EquipmentSelect { index } => {
+                gui::overworld::draw_equipment(ctx, &self.player_inventory, index);
+
+                next_status = player::equipment_select_input(self, ctx, index);
+            }
+            RunState::Blacksmith { index, crafting } => {
+                if crafting {
+                    gui::overworld::draw_crafting(ctx, &self.player_inventory, index);
+                } else {
+                    gui::overworld::draw_upgrades(ctx, &self.player_inventory, index);
                 }
+
+                next_status = player::blacksmith_input(self, ctx, index, crafting);
             }
         }
 
@@ -542,6 +577,12 @@ impl GameState for State {
 }
 
 fn main() -> rltk::BError {
+    #[cfg(feature = "bench")]
+    if std::env::args().any(|arg| arg == "--bench") {
+        bench::ai_turns_with_many_monsters();
+        return Ok(());
+    }
+
     use rltk::RltkBuilder;
     rltk::link_resource!(FONT, "resources/Zilk-16x16.png");
     rltk::link_resource!(ICONS, "resources/custom_icons.png");
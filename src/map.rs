@@ -11,12 +11,6 @@ pub enum TileType {
     NewLevel,
 }
 
-#[derive(Default, Clone)]
-struct SearchArgs {
-    search_entity: Option<Entity>,
-    multi_component: Option<Vec<crate::MonsterPart>>,
-}
-
 #[derive(Clone)]
 pub struct Map {
     pub tiles: Vec<TileType>,
@@ -31,7 +25,6 @@ pub struct Map {
     pub known_tiles: Vec<bool>,
     pub visible_tiles: Vec<bool>,
     pub blocked_tiles: Vec<bool>,
-    search_args: SearchArgs,
 }
 
 impl BaseMap for Map {
@@ -111,7 +104,6 @@ impl Map {
             known_tiles: vec![false; dim],
             visible_tiles: vec![false; dim],
             blocked_tiles: vec![false; dim],
-            search_args: SearchArgs::default(),
         }
     }
 
@@ -126,86 +118,24 @@ impl Map {
         }
     }
 
-    fn is_tile_valid(&self, x: i32, y: i32) -> bool {
-        if x < 1 || x > self.width - 1 || y < 1 || y > self.height - 1 {
-            return false;
-        }
-
-        let index = self.get_index(x, y);
-
-        // TODO: multi-tile bodies can still walk into players since player doesn't have a collision
-        // non-blocked tiles are always valid
-        if !self.blocked_tiles[index] {
-            return true;
-        }
-
-        // walls are always invalid
-        if self.tiles[index] == TileType::Wall {
-            return false;
-        }
-
-        // blocked tiles can be valid if they belong to the search_entity (creatures are not blocked by themselves)
-        let result = match self.search_args.search_entity {
-            Some(search_entity) => match self.creature_map.get(&index) {
-                Some(map_entity) => *map_entity == search_entity,
-                None => false,
-            },
-            None => false,
-        };
-
-        result
-    }
-
     fn is_exit_valid(&self, x: i32, y: i32) -> bool {
-        if !self.is_tile_valid(x, y) {
+        if x < 1 || x > self.width - 1 || y < 1 || y > self.height - 1 {
             return false;
         }
 
-        // if search_entity is a multi-tile entity, test the actual move first
-        if let Some(multitiles) = &self.search_args.multi_component {
-            for part in multitiles {
-                for part_pos in part.symbol_map.keys() {
-                    let new_x = x + part_pos.x;
-                    let new_y = y + part_pos.y;
-
-                    if !self.is_tile_valid(new_x, new_y) {
-                        return false;
-                    }
-                }
-            }
-        }
-
-        true
-    }
-
-    pub fn set_additional_args(
-        &mut self,
-        entity: Entity,
-        multi_component: Option<&crate::MultiTile>,
-    ) {
-        self.search_args.search_entity = Some(entity);
-        self.search_args.multi_component = multi_component.map(|comp| comp.part_list.clone());
+        !self.blocked_tiles[self.get_index(x, y)]
     }
 
+    // whether a creature can move to (x, y), creatures are not blocked by themselves
     pub fn is_exit_valid_for(
-        &mut self,
+        &self,
         x: i32,
         y: i32,
         entity: Entity,
         multi_component: Option<&crate::MultiTile>,
     ) -> bool {
-        self.set_additional_args(entity, multi_component);
-        self.is_exit_valid(x, y)
-    }
-
-    pub fn get_available_exits_for(
-        &mut self,
-        idx: usize,
-        entity: Entity,
-        multi_component: Option<&crate::MultiTile>,
-    ) -> rltk::SmallVec<[(usize, f32); 10]> {
-        self.set_additional_args(entity, multi_component);
-        self.get_available_exits(idx)
+        let footprint = crate::flow_field::get_footprint(multi_component);
+        crate::flow_field::can_stand(self, entity, &footprint, Point::new(x, y))
     }
 
     pub(crate) fn build_room(&mut self, room: Rect) {
@@ -76,12 +76,6 @@ pub fn build_level(ecs: &mut specs::World, width: i32, height: i32, depth: i32)
 
     for room in cloned_rooms.iter() {
         let quality = depth;
-        let mut spawn_ary = Vec::new();
-        spawn_ary.push(
-            spawn::spawner::build_mook
-                as for<'r> fn(&'r mut specs::World, rltk::Point) -> specs::Entity,
-        );
-        spawn_ary.push(spawn::spawner::build_archer);
         // spawner.build(
         //     &room,
         //     0 + quality / 2,
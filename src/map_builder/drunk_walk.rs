@@ -1,8 +1,12 @@
 use super::common::*;
 use super::MapBuilder;
 use crate::*;
+use rltk::Algorithm2D;
 use std::collections::HashMap;
 
+// chance a quest target is found asleep in its area
+const SLEEPING_TARGET_CHANCE: f32 = 0.5;
+
 #[derive(PartialEq, Copy, Clone)]
 pub enum DrunkSpawnMode {
     StartingPoint,
@@ -46,11 +50,20 @@ impl MapBuilder for DrunkardsWalkBuilder {
         {
             // spawn exactly 1 of each in the major monster list
             for name in &spawn_info.major_monsters {
-                let map_index = {
+                let (map_index, nest_index, asleep) = {
                     let mut rng = ecs.fetch_mut::<rltk::RandomNumberGenerator>();
                     let random_area = rng.range(0, self.noise_areas.len());
                     let random_spawn = &self.noise_areas.iter().nth(random_area).unwrap();
-                    random_spawn.1[rng.range(0, random_spawn.1.len())]
+
+                    // monsters run back to some other area when hurt
+                    let nest_area = rng.range(0, self.noise_areas.len());
+                    let random_nest = &self.noise_areas.iter().nth(nest_area).unwrap();
+
+                    (
+                        random_spawn.1[rng.range(0, random_spawn.1.len())],
+                        random_nest.1[rng.range(0, random_nest.1.len())],
+                        rng.rand::<f32>() < SLEEPING_TARGET_CHANCE,
+                    )
                 };
 
                 let entity = spawn::spawner::build_from_name(ecs, name, map_index);
@@ -60,6 +73,23 @@ impl MapBuilder for DrunkardsWalkBuilder {
                         // mark as a target
                         let mut targets = ecs.write_storage::<crate::MissionTarget>();
                         targets.insert(entity, MissionTarget).ok();
+
+                        let mut nests = ecs.write_storage::<crate::Nest>();
+                        nests
+                            .insert(
+                                entity,
+                                crate::Nest {
+                                    point: self.map.index_to_point2d(nest_index),
+                                },
+                            )
+                            .ok();
+
+                        if asleep {
+                            let mut ai_states = ecs.write_storage::<crate::AiState>();
+                            if let Some(state) = ai_states.get_mut(entity) {
+                                state.status = Behavior::Sleep;
+                            }
+                        }
                     }
 
                     spawn::spawner::track_entity(ecs, entity, map_index);
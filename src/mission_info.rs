@@ -1,13 +1,21 @@
+use rltk::Point;
 use specs::Entity;
+use std::collections::HashMap;
 
 pub struct MissionInfo {
     pub remaining: Vec<Entity>,
+    // materials carved during this mission
+    pub materials: HashMap<String, u32>,
+    // where each target was when the player last saw it
+    pub last_seen: HashMap<Entity, Point>,
 }
 
 impl MissionInfo {
     pub fn new() -> Self {
         Self {
             remaining: Vec::new(),
+            materials: HashMap::new(),
+            last_seen: HashMap::new(),
         }
     }
 
@@ -19,13 +27,21 @@ impl MissionInfo {
         if let Some(index) = self.remaining.iter().position(|value| *value == entity) {
             self.remaining.swap_remove(index);
         }
+
+        self.last_seen.remove(&entity);
     }
 
     pub fn is_done(&self) -> bool {
         self.remaining.is_empty()
     }
 
+    pub fn add_material(&mut self, name: &str) {
+        *self.materials.entry(name.to_string()).or_insert(0) += 1;
+    }
+
     pub fn reset(&mut self) {
         self.remaining = Vec::new();
+        self.materials = HashMap::new();
+        self.last_seen = HashMap::new();
     }
 }
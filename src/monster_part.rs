@@ -1,10 +1,94 @@
+use crate::{AttackType, DamageKind};
+use serde::Deserialize;
 use std::collections::HashMap;
 
+// hits at or above this multiplier are on a weak point
+pub const WEAK_POINT_MULT: i32 = 150;
+// hits at or below this multiplier bounce off without doing damage
+pub const DEFLECT_MULT: i32 = 40;
+
+#[derive(Deserialize, Copy, Clone, PartialEq)]
+pub enum PartKind {
+    Body,
+    Head,
+    // tails come off when broken and can be carved separately
+    Tail,
+}
+
+impl PartKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PartKind::Body => "body",
+            PartKind::Head => "head",
+            PartKind::Tail => "tail",
+        }
+    }
+}
+
+// what happens to the monster when a part breaks
+#[derive(Copy, Clone, PartialEq)]
+pub enum BreakEffect {
+    // the monster can no longer use this attack
+    LoseAttack(AttackType),
+    // the monster uses this attack half as often
+    WeakenAttack(AttackType),
+    // the monster's turns come around less often
+    Slow,
+    Flee,
+}
+
 #[derive(Clone)]
 pub struct MonsterPart {
     pub symbol_map: HashMap<rltk::Point, rltk::FontCharType>,
     pub health: i32,
     pub max_health: i32,
+    pub kind: PartKind,
+    pub hitzone: Hitzone,
+    // damage kinds the player has hit this part with
+    pub discovered: Vec<DamageKind>,
+    pub on_break: Vec<BreakEffect>,
+    pub broken: bool,
+}
+
+// how much damage a part takes from each kind of attack, as a percentage
+#[derive(Deserialize, Copy, Clone)]
+#[serde(default)]
+pub struct Hitzone {
+    pub cut: i32,
+    pub blunt: i32,
+    pub pierce: i32,
+    pub elemental: i32,
+}
+
+impl Default for Hitzone {
+    fn default() -> Self {
+        Self {
+            cut: 100,
+            blunt: 100,
+            pierce: 100,
+            elemental: 100,
+        }
+    }
+}
+
+impl Hitzone {
+    pub fn multiplier(&self, kind: DamageKind) -> i32 {
+        match kind {
+            DamageKind::Cut => self.cut,
+            DamageKind::Blunt => self.blunt,
+            DamageKind::Pierce => self.pierce,
+            DamageKind::Elemental => self.elemental,
+        }
+    }
+}
+
+// scale damage by a hitzone multiplier, deflected hits do nothing
+pub fn scale_damage(amount: i32, mult: i32) -> i32 {
+    if amount <= 0 || mult <= DEFLECT_MULT {
+        0
+    } else {
+        std::cmp::max(1, (amount * mult + 50) / 100)
+    }
 }
 
 impl MonsterPart {
@@ -30,6 +114,48 @@ impl MonsterPart {
     }
 }
 
+// glyphs that change when turned a quarter turn clockwise
+const ROTATED_GLYPHS: [(char, char); 14] = [
+    ('│', '─'),
+    ('─', '│'),
+    ('┐', '┘'),
+    ('┘', '└'),
+    ('└', '┌'),
+    ('┌', '┐'),
+    ('/', '\\'),
+    ('\\', '/'),
+    ('<', '^'),
+    ('^', '>'),
+    ('>', 'v'),
+    ('v', '<'),
+    ('|', '-'),
+    ('-', '|'),
+];
+
+fn rotate_glyph(glyph: rltk::FontCharType) -> rltk::FontCharType {
+    ROTATED_GLYPHS
+        .iter()
+        .find(|(from, _)| rltk::to_cp437(*from) == glyph)
+        .map_or(glyph, |(_, to)| rltk::to_cp437(*to))
+}
+
+// turn every part clockwise around the origin by some number of quarter turns
+pub fn rotate_parts(part_list: &[MonsterPart], turns: u8) -> Vec<MonsterPart> {
+    let mut rotated = part_list.to_vec();
+
+    for part in rotated.iter_mut() {
+        for _ in 0..turns % 4 {
+            part.symbol_map = part
+                .symbol_map
+                .iter()
+                .map(|(pos, glyph)| (rltk::Point::new(-pos.y, pos.x), rotate_glyph(*glyph)))
+                .collect();
+        }
+    }
+
+    rotated
+}
+
 pub fn all_bounds(part_list: &Vec<MonsterPart>) -> rltk::Rect {
     let mut bounds = rltk::Rect::zero();
     for part in part_list {
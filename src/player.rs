@@ -1,9 +1,15 @@
+use crate::consumable::Consumable;
+use crate::data::Skill;
 use crate::weapon::WeaponButton;
 use crate::*;
 use rltk::{Point, Rltk, VirtualKeyCode};
 
 pub const DODGE_STAM_REQ: i32 = 3;
+pub const EVADE_WINDOW_TICKS: u32 = 2;
 pub const CHARGE_STAM_REQ: i32 = 2;
+pub const PERFECT_GUARD_WINDOW: u32 = 6;
+pub const HEALTH_UPGRADE_AMOUNT: i32 = 2;
+pub const CHARGE_NOISE: i32 = 5;
 
 fn try_move_player(ecs: &mut World, dx: i32, dy: i32) -> RunState {
     use std::cmp::{max, min};
@@ -52,7 +58,10 @@ fn try_move_player(ecs: &mut World, dx: i32, dy: i32) -> RunState {
                     match npc.npc_type {
                         NpcType::Blacksmith => {
                             log.add("Upgrade your equipment here");
-                            return RunState::Blacksmith;
+                            return RunState::Blacksmith {
+                                index: 0,
+                                crafting: false,
+                            };
                         }
                         NpcType::Handler => {
                             log.add("Accept missions here");
@@ -60,7 +69,14 @@ fn try_move_player(ecs: &mut World, dx: i32, dy: i32) -> RunState {
                         }
                         NpcType::Shopkeeper => {
                             log.add("Buy useful items here");
-                            return RunState::Shop;
+                            return RunState::Shop {
+                                index: 0,
+                                selling: false,
+                            };
+                        }
+                        NpcType::EquipmentBox => {
+                            log.add("Change your weapon here");
+                            return RunState::EquipmentSelect { index: 0 };
                         }
                     }
                 } else {
@@ -286,6 +302,9 @@ pub fn player_input(gs: &mut State, ctx: &mut Rltk, is_weapon_sheathed: bool) ->
             .expect("player_input called, but it is not your turn");
     };
 
+    check_counter(gs);
+    check_interrupt(gs);
+
     if gs.player_charging.0 {
         // check bool that auto-movement only happens once
         if !gs.player_charging.3 {
@@ -296,6 +315,7 @@ pub fn player_input(gs: &mut State, ctx: &mut Rltk, is_weapon_sheathed: bool) ->
             } else {
                 // process the movement once now before handling player input
                 sys_movement::MovementSystem.run_now(&gs.ecs);
+                make_noise(&gs.ecs, CHARGE_NOISE);
                 gs.player_charging.3 = true;
             }
         }
@@ -347,7 +367,82 @@ pub fn player_input(gs: &mut State, ctx: &mut Rltk, is_weapon_sheathed: bool) ->
 
         next_state
     } else {
-        handle_keys(gs, ctx, is_weapon_sheathed)
+        let next_state = handle_keys(gs, ctx, is_weapon_sheathed);
+        if next_state == RunState::Running {
+            update_guard(gs);
+        }
+
+        next_state
+    }
+}
+
+// keep the player's BlockAttack in sync with the weapon's guard state
+fn update_guard(gs: &mut State) {
+    let mut blocks = gs.ecs.write_storage::<BlockAttack>();
+    let player = gs.ecs.fetch::<Entity>();
+
+    match gs.player_inventory.weapon.block_amount() {
+        None => {
+            blocks.remove(*player);
+        }
+        Some(block_amount) => {
+            let block_amount = block_amount + gs.player_inventory.skill_level(Skill::GuardUp);
+
+            if let Some(block) = blocks.get_mut(*player) {
+                // holding the guard does not restart the perfect guard window
+                block.block_amount = block_amount;
+            } else {
+                blocks
+                    .insert(
+                        *player,
+                        BlockAttack {
+                            block_amount,
+                            perfect_window: PERFECT_GUARD_WINDOW,
+                            countered: false,
+                        },
+                    )
+                    .expect("Failed to insert block from player");
+            }
+        }
+    }
+}
+
+// if we blocked an attack at the start of a guard, open up a counter
+fn check_counter(gs: &mut State) {
+    let countered = {
+        let mut blocks = gs.ecs.write_storage::<BlockAttack>();
+        let player = gs.ecs.fetch::<Entity>();
+
+        match blocks.get_mut(*player) {
+            Some(block) if block.countered => {
+                block.countered = false;
+                true
+            }
+            _ => false,
+        }
+    };
+
+    if countered {
+        gs.player_inventory.weapon.counter();
+
+        let mut log = gs.ecs.fetch_mut::<GameLog>();
+        log.add("Perfect guard!");
+    }
+}
+
+// getting hit breaks the player out of any held actions
+fn check_interrupt(gs: &mut State) {
+    let was_hit = {
+        let mut hit_flags = gs.ecs.write_storage::<HitFlag>();
+        let player = gs.ecs.fetch::<Entity>();
+        hit_flags.remove(*player).is_some()
+    };
+
+    if was_hit && gs.player_inventory.weapon.charge_level().is_some() {
+        gs.player_inventory.weapon.interrupt();
+
+        let mut log = gs.ecs.fetch_mut::<GameLog>();
+        log.add("Your charge was interrupted!");
     }
 }
 
@@ -399,14 +494,39 @@ pub fn can_dodge(gs: &State) -> bool {
     let stams = gs.ecs.read_storage::<Stamina>();
     let player = gs.ecs.fetch::<Entity>();
     let stamina = stams.get(*player).unwrap();
-    stamina.current >= DODGE_STAM_REQ
+    stamina.current >= dodge_cost(&gs.ecs)
+}
+
+pub fn dodge_cost(ecs: &World) -> i32 {
+    let bonuses = ecs.read_storage::<StatBonus>();
+    let player = ecs.fetch::<Entity>();
+
+    match bonuses.get(*player) {
+        Some(bonus) => std::cmp::max(1, DODGE_STAM_REQ - bonus.stamina_discount),
+        None => DODGE_STAM_REQ,
+    }
+}
+
+// let monsters nearby know where the player is
+fn make_noise(ecs: &World, radius: i32) {
+    let player = ecs.fetch::<Entity>();
+    let positions = ecs.read_storage::<Position>();
+    let mut noise_queue = ecs.write_resource::<crate::sys_awareness::NoiseQueue>();
+
+    if let Some(pos) = positions.get(*player) {
+        noise_queue.noises.push(crate::sys_awareness::Noise {
+            point: pos.as_point(),
+            radius,
+        });
+    }
 }
 
 fn reduce_stam_for_dodge(ecs: &mut World) {
+    let cost = dodge_cost(ecs);
     let mut stams = ecs.write_storage::<Stamina>();
     let player = ecs.fetch::<Entity>();
     let mut stamina = stams.get_mut(*player).unwrap();
-    stamina.current -= DODGE_STAM_REQ;
+    stamina.current -= cost;
     stamina.recover = false;
 }
 
@@ -441,6 +561,10 @@ pub fn end_turn_cleanup(ecs: &mut World) {
 }
 
 fn handle_keys(gs: &mut State, ctx: &mut Rltk, is_weapon_sheathed: bool) -> RunState {
+    if gs.player_inventory.weapon.charge_level().is_some() {
+        return handle_held_keys(gs, ctx);
+    }
+
     match ctx.key {
         None => RunState::AwaitingInput,
         Some(key) => match key {
@@ -490,6 +614,19 @@ fn handle_keys(gs: &mut State, ctx: &mut Rltk, is_weapon_sheathed: bool) -> RunS
             VirtualKeyCode::C => {
                 return weapon_attack(gs, WeaponButton::Special);
             }
+            VirtualKeyCode::G => {
+                if gs.player_inventory.weapon.guard() {
+                    RunState::Running
+                } else {
+                    RunState::AwaitingInput
+                }
+            }
+            VirtualKeyCode::A => load_coating(gs),
+            VirtualKeyCode::Q => use_item(gs),
+            VirtualKeyCode::E => {
+                gs.player_inventory.selected_item += 1;
+                RunState::AwaitingInput
+            }
             VirtualKeyCode::S => {
                 if gs.player_inventory.weapon.sheathe() {
                     return RunState::Running;
@@ -522,6 +659,7 @@ fn handle_keys(gs: &mut State, ctx: &mut Rltk, is_weapon_sheathed: bool) -> RunS
                     }
                     apply_invuln(&mut gs.ecs);
                     reduce_stam_for_dodge(&mut gs.ecs);
+                    make_noise(&gs.ecs, crate::attack_type::get_noise(AttackType::Dodge));
                     gs.player_inventory.weapon.reset();
 
                     return RunState::Running;
@@ -534,13 +672,136 @@ fn handle_keys(gs: &mut State, ctx: &mut Rltk, is_weapon_sheathed: bool) -> RunS
     }
 }
 
+// coat the next arrow with the first coating we are carrying
+fn load_coating(gs: &mut State) -> RunState {
+    let coating = gs
+        .player_inventory
+        .consumables
+        .iter()
+        .find_map(|item| match item {
+            Consumable::Coating(coating) => Some(*coating),
+            _ => None,
+        });
+
+    match coating {
+        None => {
+            let mut log = gs.ecs.fetch_mut::<GameLog>();
+            log.add("You don't have any coatings");
+            RunState::AwaitingInput
+        }
+        Some(coating) => load_selected_coating(gs, coating),
+    }
+}
+
+fn load_selected_coating(gs: &mut State, coating: crate::weapon::Coating) -> RunState {
+    let mut log = gs.ecs.fetch_mut::<GameLog>();
+
+    if gs.player_inventory.weapon.load_coating(coating) {
+        gs.player_inventory
+            .remove_item(Consumable::Coating(coating));
+        log.add(&format!("Loaded {}", coating.name()));
+        RunState::Running
+    } else {
+        log.add("Can't load a coating right now");
+        RunState::AwaitingInput
+    }
+}
+
+// use the selected item, spending the turn
+fn use_item(gs: &mut State) -> RunState {
+    let item = match gs.player_inventory.selected_item() {
+        None => {
+            let mut log = gs.ecs.fetch_mut::<GameLog>();
+            log.add("You don't have any items");
+            return RunState::AwaitingInput;
+        }
+        Some(item) => item,
+    };
+
+    let player = *gs.ecs.fetch::<Entity>();
+
+    match item {
+        Consumable::Potion | Consumable::MegaPotion => {
+            let amount = match item {
+                Consumable::MegaPotion => crate::consumable::MEGA_POTION_HEAL,
+                _ => crate::consumable::POTION_HEAL,
+            };
+
+            let mut healths = gs.ecs.write_storage::<Health>();
+            if let Some(health) = healths.get_mut(player) {
+                health.heal(amount);
+            }
+        }
+        Consumable::StaminaRation => {
+            let mut stams = gs.ecs.write_storage::<Stamina>();
+            if let Some(stamina) = stams.get_mut(player) {
+                stamina.current = stamina.max;
+            }
+        }
+        Consumable::FlashBomb => {
+            let pos = {
+                let positions = gs.ecs.read_storage::<Position>();
+                positions.get(player).unwrap().as_point()
+            };
+
+            let mut attacks = gs.ecs.write_storage::<AttackIntent>();
+            attacks
+                .insert(
+                    player,
+                    crate::attack_type::get_attack_intent(AttackType::FlashBomb, pos, None),
+                )
+                .expect("Failed to insert flash bomb");
+        }
+        Consumable::Whetstone => {
+            let mut statuses = gs.ecs.write_storage::<StatusEffects>();
+            status::inflict(
+                &mut statuses,
+                player,
+                StatusKind::Sharpened,
+                0,
+                crate::consumable::SHARPEN_DURATION,
+            );
+        }
+        Consumable::Coating(coating) => return load_selected_coating(gs, coating),
+    }
+
+    gs.player_inventory.remove_item(item);
+
+    let mut log = gs.ecs.fetch_mut::<GameLog>();
+    log.add(&format!("You use a {}", item.name()));
+
+    RunState::Running
+}
+
+// while the heavy button is held, the player can only keep holding it or let go
+fn handle_held_keys(gs: &mut State, ctx: &mut Rltk) -> RunState {
+    match ctx.key {
+        None => RunState::AwaitingInput,
+        Some(key) => match key {
+            VirtualKeyCode::X => weapon_attack(gs, WeaponButton::Heavy),
+            VirtualKeyCode::Z => weapon_attack(gs, WeaponButton::Light),
+            _ => RunState::AwaitingInput,
+        },
+    }
+}
+
 fn apply_invuln(ecs: &mut World) {
-    let mut invulns = ecs.write_storage::<Invulnerable>();
+    let mut statuses = ecs.write_storage::<StatusEffects>();
+    let bonuses = ecs.read_storage::<StatBonus>();
     let player = ecs.fetch::<Entity>();
 
-    invulns
-        .insert(*player, Invulnerable { duration: 6 }) // 24 / 4 = 6 ticks
-        .expect("Failed to make player invulnerable");
+    let mut duration = 6; // 24 / 4 = 6 ticks
+    if let Some(bonus) = bonuses.get(*player) {
+        duration += bonus.evade;
+    }
+
+    status::inflict(
+        &mut statuses,
+        *player,
+        StatusKind::Invulnerable,
+        0,
+        duration,
+    );
 }
 
 pub enum SelectionResult {
@@ -780,3 +1041,244 @@ pub fn mission_select_input(gs: &mut State, ctx: &mut Rltk, index: usize) -> Run
         index: new_index % max_index,
     }
 }
+
+pub fn equipment_select_input(gs: &mut State, ctx: &mut Rltk, index: usize) -> RunState {
+    let mut new_index = index;
+    let weapon_count = gs.player_inventory.weapon_box.len();
+    let max_index = weapon_count + gs.player_inventory.armor_box.len();
+
+    if max_index == 0 {
+        return match ctx.key {
+            Some(VirtualKeyCode::Escape) => RunState::Running,
+            _ => RunState::EquipmentSelect { index: 0 },
+        };
+    }
+
+    match ctx.key {
+        None => {}
+        Some(key) => match key {
+            VirtualKeyCode::Up | VirtualKeyCode::Numpad8 | VirtualKeyCode::K => {
+                if new_index > 0 {
+                    new_index -= 1;
+                } else {
+                    new_index += max_index - 1;
+                }
+            }
+            VirtualKeyCode::Down | VirtualKeyCode::Numpad2 | VirtualKeyCode::J => {
+                new_index += 1;
+            }
+            VirtualKeyCode::Escape => {
+                return RunState::Running;
+            }
+            VirtualKeyCode::Space | VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => {
+                // armor is listed after the weapons
+                let equipped_name = if index < weapon_count {
+                    gs.player_inventory.equip_weapon(index);
+                    gs.player_inventory.weapon.name()
+                } else {
+                    let piece = gs.player_inventory.armor_box[index - weapon_count];
+                    gs.player_inventory.equip_stored_armor(index - weapon_count);
+                    piece.name.clone()
+                };
+                update_stat_bonus(gs);
+
+                let mut log = gs.ecs.fetch_mut::<GameLog>();
+                log.add(&format!("You equip your {}", equipped_name));
+            }
+            _ => {}
+        },
+    }
+
+    RunState::EquipmentSelect {
+        index: new_index % max_index,
+    }
+}
+
+pub fn blacksmith_input(gs: &mut State, ctx: &mut Rltk, index: usize, crafting: bool) -> RunState {
+    let upgrades = crate::data::get_upgrades();
+    let recipes = crate::data::get_recipes();
+    let mut new_index = index;
+    let max_index = if crafting {
+        recipes.len()
+    } else {
+        upgrades.len()
+    };
+
+    // nothing to pick, but the other tab may still have entries
+    if max_index == 0 {
+        return match ctx.key {
+            Some(VirtualKeyCode::Escape) => RunState::Running,
+            Some(VirtualKeyCode::Tab) => RunState::Blacksmith {
+                index: 0,
+                crafting: !crafting,
+            },
+            _ => RunState::Blacksmith { index: 0, crafting },
+        };
+    }
+
+    match ctx.key {
+        None => {}
+        Some(key) => match key {
+            VirtualKeyCode::Up | VirtualKeyCode::Numpad8 | VirtualKeyCode::K => {
+                if new_index > 0 {
+                    new_index -= 1;
+                } else {
+                    new_index += max_index - 1;
+                }
+            }
+            VirtualKeyCode::Down | VirtualKeyCode::Numpad2 | VirtualKeyCode::J => {
+                new_index += 1;
+            }
+            VirtualKeyCode::Escape => {
+                return RunState::Running;
+            }
+            VirtualKeyCode::Tab => {
+                return RunState::Blacksmith {
+                    index: 0,
+                    crafting: !crafting,
+                };
+            }
+            VirtualKeyCode::Space | VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => {
+                if crafting {
+                    craft_recipe(gs, &recipes[index]);
+                } else {
+                    buy_upgrade(gs, &upgrades[index]);
+                }
+            }
+            _ => {}
+        },
+    }
+
+    RunState::Blacksmith {
+        index: new_index % max_index,
+        crafting,
+    }
+}
+
+fn craft_recipe(gs: &mut State, recipe: &crate::data::RecipeInfo) {
+    {
+        let mut log = gs.ecs.fetch_mut::<GameLog>();
+
+        if gs.player_inventory.owns(recipe) {
+            log.add(&format!("You already own the {}", recipe.name));
+            return;
+        } else if !gs.player_inventory.can_afford(&recipe.cost) {
+            log.add(&format!("You can't afford the {}", recipe.name));
+            return;
+        }
+
+        gs.player_inventory.craft(recipe);
+        log.add(&format!("Crafted the {}", recipe.name));
+    }
+
+    // crafted armor is worn right away
+    update_stat_bonus(gs);
+}
+
+fn buy_upgrade(gs: &mut State, upgrade: &crate::data::UpgradeInfo) {
+    let mut log = gs.ecs.fetch_mut::<GameLog>();
+    let level = gs.player_inventory.upgrade_level(upgrade.kind);
+
+    let cost = match upgrade.costs.get(level) {
+        None => {
+            log.add(&format!("{} is fully upgraded", upgrade.name));
+            return;
+        }
+        Some(cost) => cost,
+    };
+
+    if !gs.player_inventory.can_afford(cost) {
+        log.add("You can't afford that");
+        return;
+    }
+
+    gs.player_inventory.pay(cost);
+    gs.player_inventory.apply_upgrade(upgrade.kind);
+    log.add(&format!("{} upgraded to level {}", upgrade.name, level + 1));
+
+    if upgrade.kind == crate::data::UpgradeKind::Health {
+        let player = gs.ecs.fetch::<Entity>();
+        let mut healths = gs.ecs.write_storage::<Health>();
+        if let Some(health) = healths.get_mut(*player) {
+            health.max += HEALTH_UPGRADE_AMOUNT;
+            health.current += HEALTH_UPGRADE_AMOUNT;
+        }
+    }
+}
+
+// copy upgrades from the inventory onto the player so the attack system can see them
+pub fn update_stat_bonus(gs: &mut State) {
+    let player = gs.ecs.fetch::<Entity>();
+    let mut bonuses = gs.ecs.write_storage::<StatBonus>();
+
+    bonuses
+        .insert(
+            *player,
+            StatBonus {
+                attack: gs.player_inventory.weapon.attack_bonus()
+                    + gs.player_inventory.skill_level(Skill::AttackBoost) as i32,
+                defense: gs.player_inventory.defense(),
+                evade: EVADE_WINDOW_TICKS * gs.player_inventory.skill_level(Skill::EvadeWindow),
+                stamina_discount: gs.player_inventory.skill_level(Skill::Constitution) as i32,
+            },
+        )
+        .expect("Failed to insert StatBonus");
+}
+
+pub fn shop_input(gs: &mut State, ctx: &mut Rltk, index: usize, selling: bool) -> RunState {
+    let items = if selling {
+        gs.player_inventory.owned_items()
+    } else {
+        crate::consumable::SHOP_STOCK.to_vec()
+    };
+    let mut new_index = index;
+    let max_index = std::cmp::max(items.len(), 1);
+
+    match ctx.key {
+        None => {}
+        Some(key) => match key {
+            VirtualKeyCode::Up | VirtualKeyCode::Numpad8 | VirtualKeyCode::K => {
+                if new_index > 0 {
+                    new_index -= 1;
+                } else {
+                    new_index += max_index - 1;
+                }
+            }
+            VirtualKeyCode::Down | VirtualKeyCode::Numpad2 | VirtualKeyCode::J => {
+                new_index += 1;
+            }
+            VirtualKeyCode::Tab => {
+                return RunState::Shop {
+                    index: 0,
+                    selling: !selling,
+                };
+            }
+            VirtualKeyCode::Escape => {
+                return RunState::Running;
+            }
+            VirtualKeyCode::Space | VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => {
+                if let Some(item) = items.get(index) {
+                    let mut log = gs.ecs.fetch_mut::<GameLog>();
+
+                    if selling {
+                        gs.player_inventory.remove_item(*item);
+                        gs.player_inventory.money += item.sell_price();
+                        log.add(&format!("Sold a {}", item.name()));
+                    } else if gs.player_inventory.money >= item.price() {
+                        gs.player_inventory.money -= item.price();
+                        gs.player_inventory.consumables.push(*item);
+                        log.add(&format!("Bought a {}", item.name()));
+                    } else {
+                        log.add("You can't afford that");
+                    }
+                }
+            }
+            _ => {}
+        },
+    }
+
+    RunState::Shop {
+        index: new_index % max_index,
+        selling,
+    }
+}
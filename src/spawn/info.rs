@@ -12,13 +12,15 @@ pub fn generate_spawn_info(
 ) -> SpawnInfo {
     let mut curr_difficulty = 0;
     let mut major_monsters = Vec::new();
+    let (minor, major): (Vec<_>, Vec<_>) = crate::data::get_monsters()
+        .iter()
+        .partition(|info| info.minor);
 
     while curr_difficulty < target_difficulty {
-        let rand_index = rng.range(0, super::spawner::MONSTERS.len());
-        let (name, (difficulty, _)) = super::spawner::MONSTERS.iter().nth(rand_index).unwrap();
+        let info = major[rng.range(0, major.len())];
 
-        curr_difficulty += difficulty;
-        major_monsters.push(name.clone());
+        curr_difficulty += info.difficulty;
+        major_monsters.push(info.name.clone());
 
         // chance to early quit
         if rng.rand::<f32>() < curr_difficulty as f32 / target_difficulty as f32 {
@@ -28,7 +30,7 @@ pub fn generate_spawn_info(
 
     SpawnInfo {
         major_monsters,
-        minor_monsters: vec![],
+        minor_monsters: minor.iter().map(|info| info.name.clone()).collect(),
         resources: vec![],
         difficulty: curr_difficulty,
     }
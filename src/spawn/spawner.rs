@@ -1,34 +1,18 @@
+use crate::data::MonsterInfo;
 use crate::*;
 use rltk::{Algorithm2D, Point};
 use std::collections::HashMap;
 
 const MAX_MONSTERS: i32 = 4;
+// packs include the leader
+const PACK_MIN: i32 = 2;
+const PACK_MAX: i32 = 4;
+// how far from the leader the rest of the pack starts
+const PACK_SPREAD: i32 = 2;
 
-type Spawner = Box<for<'r> fn(&'r mut World, Point) -> Entity>;
-
-lazy_static! {
-    pub static ref MONSTERS: HashMap<String, (i32, Spawner)> = load_monster_table();
-}
-
-fn load_monster_table() -> HashMap<String, (i32, Spawner)> {
-    let mut table = HashMap::new();
-
-    table.insert(
-        "Pusher".to_string(),
-        (
-            1,
-            Box::new(build_mook as for<'r> fn(&'r mut specs::World, rltk::Point) -> specs::Entity),
-        ),
-    );
-    table.insert("Archer".to_string(), (2, Box::new(build_archer)));
-    table.insert("Crab".to_string(), (1, Box::new(build_crab)));
-
-    table
-}
-
-pub fn build_from_name(ecs: &mut World, name: &String, index: usize) -> Option<Entity> {
+pub fn build_from_name(ecs: &mut World, name: &str, index: usize) -> Option<Entity> {
     let point = { ecs.fetch::<Map>().index_to_point2d(index) };
-    MONSTERS.get(name).map(|(_, builder)| builder(ecs, point))
+    crate::data::get_monster_info(name).map(|info| build_monster(ecs, info, point))
 }
 
 /// Fills a region with stuff!
@@ -63,11 +47,22 @@ pub fn spawn_region(ecs: &mut World, area: &[usize], spawn_info: &crate::SpawnIn
     }
 
     // Actually spawn the monsters
+    // every pack member counts towards the region's spawns, so packs shrink to fit
+    let mut budget = spawn_points.len() as i32;
     for (map_idx, name) in spawn_points.iter() {
-        //let point = map.index_to_point2d(*map_idx);
+        if budget <= 0 {
+            break;
+        }
+
+        let is_minor = crate::data::get_monster_info(name).is_some_and(|info| info.minor);
+        if is_minor {
+            budget -= spawn_pack(ecs, name, *map_idx, &mut areas, budget) as i32;
+            continue;
+        }
+
+        budget -= 1;
+
         let entity = build_from_name(ecs, name, *map_idx);
-        // track_entity(ecs, &mut *map, entity, point);
-        // spawn_entity(ecs, &spawn);
 
         // track the entity if we built one
         if let Some(entity) = entity {
@@ -76,6 +71,80 @@ pub fn spawn_region(ecs: &mut World, area: &[usize], spawn_info: &crate::SpawnIn
     }
 }
 
+// spawn a leader and its followers on open tiles around it, up to max_size in total
+// followers are taken from the unused tiles in the region
+// returns how many creatures were spawned
+pub fn spawn_pack(
+    ecs: &mut World,
+    name: &str,
+    leader_idx: usize,
+    free_tiles: &mut Vec<usize>,
+    max_size: i32,
+) -> usize {
+    let leader_point = { ecs.fetch::<Map>().index_to_point2d(leader_idx) };
+    let leader = match build_from_name(ecs, name, leader_idx) {
+        Some(leader) => leader,
+        None => return 0,
+    };
+    track_entity(ecs, leader, leader_idx);
+
+    let pack_size = {
+        let mut rng = ecs.fetch_mut::<rltk::RandomNumberGenerator>();
+        i32::min(rng.range(PACK_MIN, PACK_MAX + 1), max_size)
+    };
+    let mut members = vec![leader];
+
+    while (members.len() as i32) < pack_size {
+        let next_tile = {
+            let map = ecs.fetch::<Map>();
+            free_tiles.iter().position(|index| {
+                let point = map.index_to_point2d(*index);
+                !map.blocked_tiles[*index]
+                    && (point.x - leader_point.x).abs() <= PACK_SPREAD
+                    && (point.y - leader_point.y).abs() <= PACK_SPREAD
+            })
+        };
+
+        // no room for the rest of the pack
+        let index = match next_tile {
+            Some(position) => free_tiles.remove(position),
+            None => break,
+        };
+
+        if let Some(follower) = build_from_name(ecs, name, index) {
+            track_entity(ecs, follower, index);
+            members.push(follower);
+        }
+    }
+
+    let mut pack_members = ecs.write_storage::<PackMember>();
+    let mut nests = ecs.write_storage::<Nest>();
+    for (slot, member) in members.iter().enumerate() {
+        pack_members
+            .insert(
+                *member,
+                PackMember {
+                    leader,
+                    slot,
+                    wait: 0,
+                },
+            )
+            .expect("Failed to insert PackMember");
+
+        // the pack regroups where it started
+        nests
+            .insert(
+                *member,
+                Nest {
+                    point: leader_point,
+                },
+            )
+            .expect("Failed to insert Nest");
+    }
+
+    members.len()
+}
+
 pub fn track_entity(ecs: &mut World, entity: Entity, map_idx: usize) {
     let mut map = ecs.fetch_mut::<Map>();
     let multis = ecs.read_storage::<MultiTile>();
@@ -153,181 +222,99 @@ pub fn build_player(ecs: &mut World, point: Point) -> Entity {
 pub fn build_enemy_base(ecs: &mut World) -> EntityBuilder {
     ecs.create_entity()
         .with(ViewableIndex { list_index: None })
-        .with(Schedulable {
-            current: 0,
-            base: 24,
-            delta: 4,
-        })
-        .with(Viewshed {
-            visible: Vec::new(),
-            dirty: true,
-            range: 10,
-        })
         .with(BlocksTile)
+        .with(Awareness::default())
+        .with(StuckInfo::default())
         .with(AiState {
             status: Behavior::Wander,
-            prev_path: None,
-            path_step: 0,
+        })
+        .with(StunMeter {
+            current: 0,
+            threshold: 10,
         })
 }
 
-pub fn build_mook(ecs: &mut World, point: Point) -> Entity {
-    let part_list = vec![
-        MonsterPart {
-            symbol_map: HashMap::from([
-                (rltk::Point::new(-1, 0), rltk::to_cp437('│')),
-                (rltk::Point::new(-1, 1), rltk::to_cp437('└')),
-                (rltk::Point::new(0, 1), rltk::to_cp437('─')),
-            ]),
-            health: 4,
-            max_health: 4,
-        },
-        MonsterPart {
-            symbol_map: HashMap::from([
-                (rltk::Point::new(0, -1), rltk::to_cp437('─')),
-                (rltk::Point::new(1, -1), rltk::to_cp437('┐')),
-                (rltk::Point::new(1, 0), rltk::to_cp437('│')),
-            ]),
-            health: 4,
-            max_health: 4,
-        },
-    ];
-
-    build_enemy_base(ecs)
+// build a monster from its entry in the monster data
+pub fn build_monster(ecs: &mut World, info: &'static MonsterInfo, point: Point) -> Entity {
+    let part_list: Vec<MonsterPart> = info
+        .parts
+        .iter()
+        .map(|part| MonsterPart {
+            symbol_map: part
+                .tiles
+                .iter()
+                .map(|tile| (Point::new(tile.x, tile.y), glyph(&tile.glyph)))
+                .collect(),
+            health: part.health,
+            max_health: part.health,
+            kind: part.kind,
+            hitzone: part.hitzone,
+            discovered: vec![],
+            on_break: part
+                .on_break
+                .iter()
+                .map(|effect| effect.to_break_effect())
+                .collect(),
+            broken: false,
+        })
+        .collect();
+
+    let mut builder = build_enemy_base(ecs)
         .with(Position {
             x: point.x,
             y: point.y,
         })
         .with(Renderable {
-            symbol: rltk::to_cp437('x'),
-            fg: RGB::named(rltk::LIGHT_BLUE),
+            symbol: glyph(&info.glyph),
+            fg: RGB::from_hex(&info.fg).expect("Invalid monster color"),
             bg: RGB::named(rltk::BLACK),
         })
         .with(Viewable {
-            name: "Pusher".to_string(),
-            description: vec![],
+            name: info.name.clone(),
+            description: info.description.clone(),
             seen: false,
         })
         .with(Health {
-            current: 10,
-            max: 10,
-        })
-        .with(Moveset {
-            moves: vec![(AttackType::Sweep, 0.25), (AttackType::Punch, 0.75)],
-            bump_attack: AttackType::Punch,
-        })
-        .with(MultiTile {
-            bounds: all_bounds(&part_list),
-            part_list: part_list,
+            current: info.health,
+            max: info.health,
         })
-        .with(Facing {
-            direction: crate::Direction::N,
-        })
-        .build()
-}
-
-pub fn build_crab(ecs: &mut World, point: Point) -> Entity {
-    let part_list = vec![
-        MonsterPart {
-            symbol_map: HashMap::from([
-                (rltk::Point::new(1, 0), rltk::to_cp437('─')),
-                (rltk::Point::new(2, 1), rltk::to_cp437('\\')),
-            ]),
-            health: 1,
-            max_health: 1,
-        },
-        MonsterPart {
-            symbol_map: HashMap::from([
-                (rltk::Point::new(-1, 0), rltk::to_cp437('─')),
-                (rltk::Point::new(-2, 1), rltk::to_cp437('/')),
-            ]),
-            health: 1,
-            max_health: 1,
-        },
-    ];
-
-    build_enemy_base(ecs)
-        .with(Position {
-            x: point.x,
-            y: point.y,
-        })
-        .with(Renderable {
-            symbol: rltk::to_cp437('x'),
-            fg: RGB::named(rltk::LIGHT_BLUE),
-            bg: RGB::named(rltk::BLACK),
-        })
-        .with(Viewable {
-            name: "Crab".to_string(),
-            description: vec![],
-            seen: false,
+        .with(Schedulable {
+            current: 0,
+            base: 24,
+            delta: info.speed,
         })
-        .with(Health {
-            current: 10,
-            max: 10,
+        .with(Viewshed {
+            visible: Vec::new(),
+            dirty: true,
+            range: info.vision,
         })
         .with(Moveset {
-            moves: vec![(AttackType::Sweep, 0.25), (AttackType::Punch, 0.75)],
-            bump_attack: AttackType::Punch,
-        })
-        .with(MultiTile {
+            moves: info
+                .moves
+                .iter()
+                .map(|mv| (mv.attack.to_attack_type(), mv.weight))
+                .collect(),
+            bump_attack: info.bump_attack.to_attack_type(),
+        });
+
+    if !part_list.is_empty() {
+        builder = builder.with(MultiTile {
             bounds: all_bounds(&part_list),
-            part_list: part_list,
-        })
-        .with(Facing {
+            part_list,
+        });
+    }
+
+    if info.facing {
+        builder = builder.with(Facing {
             direction: crate::Direction::N,
-        })
-        .build()
+        });
+    }
+
+    builder.build()
 }
 
-pub fn build_archer(ecs: &mut World, point: Point) -> Entity {
-    let part_list = vec![
-        MonsterPart {
-            symbol_map: HashMap::from([(rltk::Point::new(-1, 0), rltk::to_cp437('<'))]),
-            health: 2,
-            max_health: 2,
-        },
-        MonsterPart {
-            symbol_map: HashMap::from([(rltk::Point::new(0, 1), rltk::to_cp437('v'))]),
-            health: 2,
-            max_health: 2,
-        },
-        MonsterPart {
-            symbol_map: HashMap::from([(rltk::Point::new(1, 0), rltk::to_cp437('>'))]),
-            health: 2,
-            max_health: 2,
-        },
-        MonsterPart {
-            symbol_map: HashMap::from([(rltk::Point::new(0, -1), rltk::to_cp437('^'))]),
-            health: 2,
-            max_health: 2,
-        },
-    ];
-
-    build_enemy_base(ecs)
-        .with(Position {
-            x: point.x,
-            y: point.y,
-        })
-        .with(Renderable {
-            symbol: rltk::to_cp437('y'),
-            fg: RGB::named(rltk::LIGHT_GREEN),
-            bg: RGB::named(rltk::BLACK),
-        })
-        .with(Viewable {
-            name: "Archer".to_string(),
-            description: vec!["A grunt with a bow".to_string()],
-            seen: false,
-        })
-        .with(Health { current: 6, max: 6 })
-        .with(MultiTile {
-            bounds: all_bounds(&part_list),
-            part_list: part_list,
-        })
-        .with(Moveset {
-            moves: vec![(AttackType::Punch, 0.25), (AttackType::Ranged, 0.75)],
-            bump_attack: AttackType::Punch,
-        })
-        .build()
+fn glyph(text: &str) -> rltk::FontCharType {
+    rltk::to_cp437(text.chars().next().expect("Empty monster glyph"))
 }
 // #endregion
 
@@ -459,3 +446,129 @@ pub fn build_npc_handler(ecs: &mut World, point: Point) -> Entity {
         })
         .build()
 }
+
+pub fn build_equipment_box(ecs: &mut World, point: Point) -> Entity {
+    build_npc_base(ecs, point)
+        .with(Renderable {
+            symbol: rltk::to_cp437('■'),
+            fg: RGB::named(rltk::BURLYWOOD),
+            bg: RGB::named(rltk::BLACK),
+        })
+        .with(Viewable {
+            name: "Equipment Box".to_string(),
+            description: vec!["Your weapons".to_string(), "are stored here".to_string()],
+            seen: false,
+        })
+        .with(Npc {
+            npc_type: NpcType::EquipmentBox,
+        })
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn register_monster_components(world: &mut World) {
+        world.register::<ViewableIndex>();
+        world.register::<BlocksTile>();
+        world.register::<AiState>();
+        world.register::<Awareness>();
+        world.register::<StuckInfo>();
+        world.register::<StunMeter>();
+        world.register::<Position>();
+        world.register::<Renderable>();
+        world.register::<Viewable>();
+        world.register::<Health>();
+        world.register::<Schedulable>();
+        world.register::<Viewshed>();
+        world.register::<Moveset>();
+        world.register::<MultiTile>();
+        world.register::<Facing>();
+        world.register::<PackMember>();
+        world.register::<Nest>();
+    }
+
+    // an open map with a pack leader spawning in the middle
+    // returns the leader's tile, the floor tiles left for the rest of the pack and the monster to spawn
+    fn build_pack_world() -> (World, usize, Vec<usize>, String) {
+        let mut world = crate::test_util::build_world(20, 20, Point::new(0, 0));
+        register_monster_components(&mut world);
+
+        let (leader_idx, free_tiles) = {
+            let map = world.fetch::<Map>();
+            let leader_idx = map.get_index(10, 10);
+            let free_tiles = (0..map.tiles.len())
+                .filter(|index| *index != leader_idx && map.tiles[*index] == TileType::Floor)
+                .collect();
+            (leader_idx, free_tiles)
+        };
+
+        let name = crate::data::get_monsters()
+            .iter()
+            .find(|info| info.minor)
+            .expect("No minor monsters in the data")
+            .name
+            .clone();
+
+        (world, leader_idx, free_tiles, name)
+    }
+
+    #[test]
+    fn every_monster_builds_from_data() {
+        let mut world = World::new();
+        register_monster_components(&mut world);
+
+        for info in crate::data::get_monsters() {
+            let ent = build_monster(&mut world, info, Point::new(5, 5));
+
+            let healths = world.read_storage::<Health>();
+            assert_eq!(healths.get(ent).unwrap().max, info.health);
+
+            // quest targets always leave something to carve
+            assert!(info.minor || info.drops.is_some());
+
+            let movesets = world.read_storage::<Moveset>();
+            assert_eq!(movesets.get(ent).unwrap().moves.len(), info.moves.len());
+
+            let multis = world.read_storage::<MultiTile>();
+            assert_eq!(
+                multis.get(ent).map_or(0, |multi| multi.part_list.len()),
+                info.parts.len()
+            );
+        }
+    }
+
+    #[test]
+    fn packs_spawn_around_their_leader() {
+        let (mut world, leader_idx, mut free_tiles, name) = build_pack_world();
+        let spawned = spawn_pack(&mut world, &name, leader_idx, &mut free_tiles, PACK_MAX);
+        world.maintain();
+
+        let positions = world.read_storage::<Position>();
+        let pack_members = world.read_storage::<PackMember>();
+        let nests = world.read_storage::<Nest>();
+        let members = (&positions, &pack_members, &nests)
+            .join()
+            .collect::<Vec<_>>();
+
+        assert!(members.len() as i32 >= PACK_MIN && members.len() as i32 <= PACK_MAX);
+        assert_eq!(members.len(), spawned);
+        let leader = members[0].1.leader;
+        for (pos, member, nest) in members {
+            assert!(member.leader == leader);
+            assert!((pos.x - 10).abs() <= PACK_SPREAD && (pos.y - 10).abs() <= PACK_SPREAD);
+            assert_eq!(nest.point, Point::new(10, 10));
+        }
+    }
+
+    #[test]
+    fn packs_shrink_to_fit_the_spawn_budget() {
+        let (mut world, leader_idx, mut free_tiles, name) = build_pack_world();
+        let spawned = spawn_pack(&mut world, &name, leader_idx, &mut free_tiles, 1);
+        world.maintain();
+
+        assert_eq!(spawned, 1);
+        assert_eq!(world.read_storage::<PackMember>().join().count(), 1);
+    }
+}
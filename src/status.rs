@@ -0,0 +1,389 @@
+use rltk::RGB;
+use specs::prelude::*;
+use specs::Component;
+
+const MAX_BLEED_STACKS: i32 = 3;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum StatusKind {
+    Poison,
+    Bleed,
+    Paralysis,
+    Sleep,
+    Stun,
+    Invulnerable,
+    Sharpened,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct StatusEffect {
+    pub kind: StatusKind,
+    // damage per turn for poison and bleed, unused otherwise
+    pub amount: i32,
+    pub duration: u32,
+}
+
+impl StatusKind {
+    // the entity loses its turns while this lasts
+    pub fn skips_turn(&self) -> bool {
+        matches!(
+            self,
+            StatusKind::Paralysis | StatusKind::Sleep | StatusKind::Stun
+        )
+    }
+
+    // invulnerability counts down every tick instead of every turn
+    pub fn counts_ticks(&self) -> bool {
+        *self == StatusKind::Invulnerable
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            StatusKind::Poison => "Poisoned",
+            StatusKind::Bleed => "Bleeding",
+            StatusKind::Paralysis => "Paralyzed",
+            StatusKind::Sleep => "Asleep",
+            StatusKind::Stun => "Stunned",
+            StatusKind::Invulnerable => "Invulnerable",
+            StatusKind::Sharpened => "Sharpened",
+        }
+    }
+
+    pub fn icon(&self) -> (rltk::FontCharType, RGB) {
+        match self {
+            StatusKind::Poison => (rltk::to_cp437('p'), RGB::named(rltk::MEDIUM_PURPLE)),
+            StatusKind::Bleed => (rltk::to_cp437('b'), RGB::named(rltk::RED)),
+            StatusKind::Paralysis => (rltk::to_cp437('!'), RGB::named(rltk::YELLOW)),
+            StatusKind::Sleep => (rltk::to_cp437('z'), RGB::named(rltk::LIGHT_BLUE)),
+            StatusKind::Stun => (rltk::to_cp437('*'), RGB::named(rltk::ORANGE)),
+            StatusKind::Invulnerable => (rltk::to_cp437('i'), RGB::named(rltk::WHITE)),
+            StatusKind::Sharpened => (rltk::to_cp437('/'), RGB::named(rltk::CYAN)),
+        }
+    }
+}
+
+#[derive(Component, Default)]
+pub struct StatusEffects {
+    pub effects: Vec<StatusEffect>,
+}
+
+impl StatusEffects {
+    // bleeding stacks up to a limit, anything else keeps the strongest and longest of the two
+    pub fn add(&mut self, effect: StatusEffect) {
+        match self.effects.iter_mut().find(|e| e.kind == effect.kind) {
+            None => self.effects.push(effect),
+            Some(existing) => {
+                if effect.kind == StatusKind::Bleed {
+                    existing.amount =
+                        std::cmp::min(MAX_BLEED_STACKS, existing.amount + effect.amount);
+                } else {
+                    existing.amount = std::cmp::max(existing.amount, effect.amount);
+                }
+
+                existing.duration = std::cmp::max(existing.duration, effect.duration);
+            }
+        }
+    }
+
+    pub fn has(&self, kind: StatusKind) -> bool {
+        self.effects.iter().any(|e| e.kind == kind)
+    }
+
+    pub fn remove(&mut self, kind: StatusKind) {
+        self.effects.retain(|e| e.kind != kind);
+    }
+
+    pub fn skips_turn(&self) -> bool {
+        self.effects.iter().any(|e| e.kind.skips_turn())
+    }
+
+    // count down effects that last for some number of ticks
+    pub fn tick(&mut self) {
+        for effect in self.effects.iter_mut() {
+            if effect.kind.counts_ticks() {
+                effect.duration = effect.duration.saturating_sub(1);
+            }
+        }
+
+        self.effects
+            .retain(|e| !e.kind.counts_ticks() || e.duration > 0);
+    }
+
+    // apply damage over time and count down effects that last for some number of turns
+    // returns the damage taken and the effects that wore off
+    pub fn on_turn(&mut self) -> (i32, Vec<StatusKind>) {
+        let mut damage = 0;
+        let mut expired = Vec::new();
+
+        for effect in self.effects.iter_mut() {
+            if effect.kind.counts_ticks() {
+                continue;
+            }
+
+            if matches!(effect.kind, StatusKind::Poison | StatusKind::Bleed) {
+                damage += effect.amount;
+            }
+
+            effect.duration = effect.duration.saturating_sub(1);
+            if effect.duration == 0 {
+                expired.push(effect.kind);
+            }
+        }
+
+        self.effects.retain(|e| e.duration > 0);
+        (damage, expired)
+    }
+}
+
+// add an effect to an entity, creating its status list if needed
+pub fn inflict(
+    statuses: &mut WriteStorage<StatusEffects>,
+    ent: Entity,
+    kind: StatusKind,
+    amount: i32,
+    duration: u32,
+) {
+    if let Ok(entry) = statuses.entry(ent) {
+        entry
+            .or_insert_with(StatusEffects::default)
+            .add(StatusEffect {
+                kind,
+                amount,
+                duration,
+            });
+    }
+}
+
+// whether an entity currently has an effect
+pub fn has_status<D>(statuses: &Storage<StatusEffects, D>, ent: Entity, kind: StatusKind) -> bool
+where
+    D: std::ops::Deref<Target = specs::storage::MaskedStorage<StatusEffects>>,
+{
+    statuses.get(ent).is_some_and(|status| status.has(kind))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sys_attack::AttackSystem;
+    use crate::sys_turn::TurnSystem;
+    use rltk::Point;
+
+    fn effect(kind: StatusKind, amount: i32, duration: u32) -> StatusEffect {
+        StatusEffect {
+            kind,
+            amount,
+            duration,
+        }
+    }
+
+    fn build_world() -> World {
+        let mut world = crate::test_util::build_world(10, 10, Point::new(5, 8));
+        System::setup(&mut AttackSystem, &mut world);
+        System::setup(&mut TurnSystem, &mut world);
+        world
+    }
+
+    // a monster that takes a turn every time the turn system runs
+    fn spawn(world: &mut World, point: Point, effects: Vec<StatusEffect>) -> Entity {
+        let ent = world
+            .create_entity()
+            .with(crate::Position {
+                x: point.x,
+                y: point.y,
+            })
+            .with(crate::Health {
+                current: 10,
+                max: 10,
+            })
+            .with(crate::Schedulable {
+                current: 0,
+                base: 1,
+                delta: 1,
+            })
+            .with(crate::AiState {
+                status: crate::Behavior::Wander,
+            })
+            .with(StatusEffects { effects })
+            .build();
+
+        let mut map = world.fetch_mut::<crate::Map>();
+        let index = map.get_index(point.x, point.y);
+        map.track_creature(ent, index, None);
+
+        ent
+    }
+
+    fn run_turn(world: &mut World) {
+        TurnSystem.run_now(world);
+        world.maintain();
+    }
+
+    fn health(world: &World, ent: Entity) -> i32 {
+        world
+            .read_storage::<crate::Health>()
+            .get(ent)
+            .unwrap()
+            .current
+    }
+
+    fn has(world: &World, ent: Entity, kind: StatusKind) -> bool {
+        has_status(&world.read_storage::<StatusEffects>(), ent, kind)
+    }
+
+    #[test]
+    fn bleed_stacks_while_other_effects_refresh() {
+        let mut statuses = StatusEffects::default();
+        statuses.add(effect(StatusKind::Bleed, 1, 3));
+        statuses.add(effect(StatusKind::Bleed, 1, 5));
+        statuses.add(effect(StatusKind::Bleed, 2, 2));
+        statuses.add(effect(StatusKind::Poison, 2, 4));
+        statuses.add(effect(StatusKind::Poison, 1, 6));
+
+        assert_eq!(statuses.effects.len(), 2);
+
+        let bleed = statuses.effects[0];
+        assert_eq!(bleed.amount, MAX_BLEED_STACKS);
+        assert_eq!(bleed.duration, 5);
+
+        let poison = statuses.effects[1];
+        assert_eq!(poison.amount, 2);
+        assert_eq!(poison.duration, 6);
+    }
+
+    #[test]
+    fn effects_expire_after_their_turns() {
+        let mut world = build_world();
+        let ent = spawn(
+            &mut world,
+            Point::new(5, 5),
+            vec![effect(StatusKind::Paralysis, 0, 2)],
+        );
+
+        run_turn(&mut world);
+        assert!(has(&world, ent, StatusKind::Paralysis));
+        assert!(!world.read_storage::<crate::CanActFlag>().contains(ent));
+
+        // the last turn of paralysis is still lost
+        run_turn(&mut world);
+        assert!(!has(&world, ent, StatusKind::Paralysis));
+        assert!(!world.read_storage::<crate::CanActFlag>().contains(ent));
+
+        run_turn(&mut world);
+        assert!(world.read_storage::<crate::CanActFlag>().contains(ent));
+    }
+
+    #[test]
+    fn damage_over_time_ticks_every_turn() {
+        let mut world = build_world();
+        let ent = spawn(
+            &mut world,
+            Point::new(5, 5),
+            vec![
+                effect(StatusKind::Poison, 1, 3),
+                effect(StatusKind::Bleed, 2, 1),
+            ],
+        );
+
+        run_turn(&mut world);
+        assert_eq!(health(&world, ent), 7);
+
+        run_turn(&mut world);
+        run_turn(&mut world);
+        assert_eq!(health(&world, ent), 5);
+
+        run_turn(&mut world);
+        assert_eq!(health(&world, ent), 5);
+        assert!(world
+            .read_storage::<StatusEffects>()
+            .get(ent)
+            .unwrap()
+            .effects
+            .is_empty());
+    }
+
+    // the player shoots a coated arrow at a point
+    fn shoot(world: &mut World, coating: crate::weapon::Coating, loc: Point) {
+        let player = *world.fetch::<Entity>();
+        world
+            .write_storage::<crate::AttackIntent>()
+            .insert(
+                player,
+                crate::AttackIntent {
+                    main: crate::AttackType::ArrowHit {
+                        damage: 0,
+                        coating: Some(coating),
+                    },
+                    modifier: None,
+                    loc,
+                    delay: 0,
+                },
+            )
+            .expect("Failed to insert attack");
+        AttackSystem.run_now(world);
+        world.maintain();
+    }
+
+    #[test]
+    fn paralysis_leaves_behavior_alone() {
+        let mut world = build_world();
+        let ent = spawn(&mut world, Point::new(5, 5), vec![]);
+        world
+            .write_storage::<crate::AiState>()
+            .get_mut(ent)
+            .unwrap()
+            .status = crate::Behavior::Flee;
+
+        shoot(
+            &mut world,
+            crate::weapon::Coating::Paralysis,
+            Point::new(5, 5),
+        );
+
+        assert!(has(&world, ent, StatusKind::Paralysis));
+        assert!(matches!(
+            world
+                .read_storage::<crate::AiState>()
+                .get(ent)
+                .unwrap()
+                .status,
+            crate::Behavior::Flee
+        ));
+    }
+
+    #[test]
+    fn sleep_holds_monsters_asleep_until_it_wears_off() {
+        let mut world = build_world();
+        let ent = spawn(&mut world, Point::new(5, 5), vec![]);
+        shoot(&mut world, crate::weapon::Coating::Sleep, Point::new(5, 5));
+
+        let is_asleep = |world: &World| {
+            matches!(
+                world
+                    .read_storage::<crate::AiState>()
+                    .get(ent)
+                    .unwrap()
+                    .status,
+                crate::Behavior::Sleep
+            )
+        };
+
+        assert!(has(&world, ent, StatusKind::Sleep));
+        assert!(is_asleep(&world));
+
+        let duration = world
+            .read_storage::<StatusEffects>()
+            .get(ent)
+            .unwrap()
+            .effects[0]
+            .duration;
+        for _ in 0..duration - 1 {
+            run_turn(&mut world);
+            assert!(is_asleep(&world));
+        }
+
+        run_turn(&mut world);
+        assert!(!has(&world, ent, StatusKind::Sleep));
+        assert!(!is_asleep(&world));
+    }
+}
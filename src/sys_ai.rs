@@ -1,6 +1,40 @@
-use crate::{Map, MoveIntent};
-use rltk::Algorithm2D;
+use crate::MoveIntent;
+use rltk::{Algorithm2D, DistanceAlg};
 use specs::prelude::*;
+use std::collections::HashMap;
+
+// monsters flee once their health drops to this percent
+const FLEE_HEALTH_PERCENT: i32 = 30;
+// how close to its nest a monster needs to be to rest
+const NEST_RADIUS: f32 = 2.0;
+// how much being far from the player matters compared to getting closer to the nest
+const FLEE_WEIGHT: f32 = 1.5;
+// past this distance, fleeing monsters stop worrying about the player
+const FLEE_SAFE_DISTANCE: f32 = 10.0;
+const REST_HEAL: i32 = 1;
+// failed moves in a row before a monster tries to get unstuck
+const STUCK_THRESHOLD: u32 = 3;
+// free moves needed before a squeezed monster spreads out again
+const UNSQUEEZE_MOVES: u32 = 2;
+// free moves needed before a monster starts over with the gentlest recovery
+const STUCK_RESET_MOVES: u32 = 10;
+// how far away a monster will look for somewhere it fits
+const RELOCATE_RANGE: i32 = 5;
+// pack members next to the player before the pack attacks
+const PACK_FLANKERS: usize = 2;
+// how long a pack member holds its position before attacking anyway
+const PACK_MAX_WAIT: u32 = 3;
+// where pack members line up around the player, opposite sides first
+const FLANK_OFFSETS: [(i32, i32); 8] = [
+    (0, -1),
+    (0, 1),
+    (-1, 0),
+    (1, 0),
+    (-1, -1),
+    (1, 1),
+    (-1, 1),
+    (1, -1),
+];
 
 pub enum Behavior {
     Sleep,
@@ -10,6 +44,7 @@ pub enum Behavior {
     AttackStartup { turns_left: i32, info: AttackInfo },
     AttackRecovery { turns_left: i32, info: AttackInfo },
     Flee,
+    Rest,
 }
 
 #[derive(Clone)]
@@ -33,10 +68,18 @@ struct AiStepData<'a> {
     viewshed: &'a crate::Viewshed,
     moveset: &'a crate::Moveset,
     multi: Option<&'a crate::MultiTile>,
+    footprint: crate::flow_field::Footprint,
+    facing: Option<crate::Direction>,
+    stuck: Option<&'a mut crate::StuckInfo>,
+    pack: Option<&'a mut crate::PackMember>,
+    pack_ready: bool,
+    health: Option<&'a mut crate::Health>,
+    nest: Option<rltk::Point>,
     player_point: rltk::Point,
-    map: &'a mut crate::Map,
+    map: &'a crate::Map,
     p_builder: &'a mut crate::ParticleBuilder,
     rng: &'a mut rltk::RandomNumberGenerator,
+    flow_fields: &'a mut crate::flow_field::FlowFields,
 }
 
 pub struct AiSystem;
@@ -53,10 +96,16 @@ impl<'a> System<'a> for AiSystem {
         ReadStorage<'a, crate::Viewshed>,
         ReadStorage<'a, crate::Moveset>,
         ReadStorage<'a, crate::MultiTile>,
+        ReadStorage<'a, crate::Facing>,
+        WriteStorage<'a, crate::StuckInfo>,
+        WriteStorage<'a, crate::PackMember>,
+        WriteStorage<'a, crate::Health>,
+        ReadStorage<'a, crate::Nest>,
         ReadExpect<'a, Entity>,
-        WriteExpect<'a, crate::Map>,
+        ReadExpect<'a, crate::Map>,
         WriteExpect<'a, crate::ParticleBuilder>,
         WriteExpect<'a, rltk::RandomNumberGenerator>,
+        Write<'a, crate::flow_field::FlowFields>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
@@ -71,24 +120,60 @@ impl<'a> System<'a> for AiSystem {
             viewsheds,
             movesets,
             multis,
+            facings,
+            mut stuck_infos,
+            mut pack_members,
+            mut healths,
+            nests,
             player,
-            mut map,
+            map,
             mut p_builder,
             mut rng,
+            mut flow_fields,
         ) = data;
         let mut turn_done = Vec::new();
         let player_point = positions.get(*player).unwrap().as_point();
+        flow_fields.update(&map);
 
-        for (ent, _turn, pos, state, viewshed, moveset, multi) in (
-            &entities,
-            &can_act,
-            &positions,
-            &mut states,
-            &viewsheds,
-            &movesets,
-            (&multis).maybe(),
-        )
+        // packs scatter back to where they started once their leader is gone
+        let leaderless = (&entities, &pack_members)
             .join()
+            .filter(|(_, member)| !entities.is_alive(member.leader))
+            .map(|(ent, _)| ent)
+            .collect::<Vec<_>>();
+        for ent in leaderless {
+            pack_members.remove(ent);
+            if let Some(state) = states.get_mut(ent) {
+                state.status = Behavior::Flee;
+            }
+        }
+
+        // count up who is already next to the player in each pack
+        let mut pack_sizes: HashMap<Entity, (usize, usize)> = HashMap::new();
+        for (member, pos) in (&pack_members, &positions).join() {
+            let (size, in_position) = pack_sizes.entry(member.leader).or_insert((0, 0));
+            *size += 1;
+            if Self::next_to(pos.as_point(), player_point) {
+                *in_position += 1;
+            }
+        }
+
+        for (ent, _turn, pos, state, viewshed, moveset, multi, facing, stuck, pack, health, nest) in
+            (
+                &entities,
+                &can_act,
+                &positions,
+                &mut states,
+                &viewsheds,
+                &movesets,
+                (&multis).maybe(),
+                (&facings).maybe(),
+                (&mut stuck_infos).maybe(),
+                (&mut pack_members).maybe(),
+                (&mut healths).maybe(),
+                (&nests).maybe(),
+            )
+                .join()
         {
             let action = self.next_step(AiStepData {
                 ent,
@@ -97,10 +182,21 @@ impl<'a> System<'a> for AiSystem {
                 viewshed,
                 moveset,
                 multi,
+                footprint: crate::flow_field::get_footprint(multi),
+                facing: facing.map(|facing| facing.direction),
+                pack_ready: pack.as_ref().is_some_and(|member| {
+                    let (size, in_position) = pack_sizes[&member.leader];
+                    in_position >= std::cmp::min(size, PACK_FLANKERS)
+                }),
+                stuck,
+                pack,
+                health,
+                nest: nest.map(|nest| nest.point),
                 player_point,
-                map: &mut *map,
+                map: &map,
                 p_builder: &mut *p_builder,
                 rng: &mut *rng,
+                flow_fields: &mut flow_fields,
             });
 
             match action {
@@ -132,16 +228,27 @@ impl<'a> System<'a> for AiSystem {
 }
 
 impl AiSystem {
-    fn next_step(&mut self, data: AiStepData) -> NextIntent {
+    fn next_step(&mut self, mut data: AiStepData) -> NextIntent {
+        // only bother getting unstuck when we're trying to go somewhere
+        if matches!(
+            data.state.status,
+            Behavior::Wander | Behavior::Chase { .. } | Behavior::Flee
+        ) {
+            if let Some(intent) = Self::unstick(&mut data) {
+                return intent;
+            }
+        }
+
         loop {
             match data.state.status {
                 Behavior::Sleep => {
-                    // the do nothing state
-                    // TODO: trigger wake up
+                    // the do nothing state, the awareness system wakes us up
                     return NextIntent::None;
                 }
                 Behavior::Wander => {
-                    if Self::can_see_target(data.viewshed, data.player_point) {
+                    if Self::should_flee(&data) {
+                        data.state.status = Behavior::Flee;
+                    } else if Self::can_see_target(data.viewshed, data.player_point) {
                         data.state.status = Behavior::Chase {
                             target_point: data.player_point,
                         };
@@ -150,48 +257,39 @@ impl AiSystem {
                     }
                 }
                 Behavior::Chase { target_point } => {
-                    if Self::can_see_target(data.viewshed, data.player_point) {
+                    if Self::should_flee(&data) {
+                        data.state.status = Behavior::Flee;
+                    } else if Self::can_see_target(data.viewshed, data.player_point) {
                         // track the player's current position
                         data.state.status = Behavior::Chase {
                             target_point: data.player_point,
                         };
 
-                        // check if we have any attacks that can hit
-                        let orig_point = data.pos.as_point();
+                        // packs close in from all sides before anyone attacks
+                        if let Some(flank_point) = Self::flank_point(&mut data) {
+                            if flank_point == data.pos.as_point() {
+                                return NextIntent::None;
+                            }
 
-                        let rolled_prob: f32 = data.rng.rand();
-                        let mut cumul_prob: f32 = 0.0;
-                        let mut attack_found = false;
+                            return Self::move_towards(flank_point, data);
+                        }
 
-                        // TODO: smarter attack selection
-                        // this is fine when all of the attacks have similar attack ranges
-                        // however, we might run into cases where we are in range to attack, but we decided to use an attack thats not valid
-                        for (potential_attack, chance) in data.moveset.moves.iter() {
-                            cumul_prob += chance;
-                            if rolled_prob > cumul_prob {
-                                continue;
-                            }
+                        // get into a better spot for our most likely attack before using anything
+                        if let Some(intent) = Self::reposition(&mut data) {
+                            return intent;
+                        }
 
-                            if let Some(attack_loc) = crate::attack_type::is_attack_valid(
-                                *potential_attack,
-                                orig_point,
-                                data.player_point,
-                            ) {
+                        match Self::choose_attack(&mut data) {
+                            Some(info) => {
                                 data.state.status = Behavior::AttackStartup {
-                                    turns_left: crate::attack_type::get_startup(*potential_attack),
-                                    info: AttackInfo {
-                                        attack_type: *potential_attack,
-                                        attack_loc: attack_loc,
-                                    },
+                                    turns_left: crate::attack_type::get_startup(info.attack_type),
+                                    info,
                                 };
-                                attack_found = true;
-                                break;
                             }
-                        }
-
-                        if !attack_found {
-                            // if we can't hit, just move towards the player
-                            return Self::move_towards(data.player_point, data);
+                            None => {
+                                // if we can't hit, just move towards the player
+                                return Self::move_towards(data.player_point, data);
+                            }
                         }
                     } else {
                         // we don't see the player, move to the last tracked point
@@ -243,23 +341,136 @@ impl AiSystem {
                     }
                 }
                 Behavior::Flee => {
-                    // TODO
-                    return NextIntent::None;
+                    // without a nest, getting out of sight is good enough
+                    if Self::at_nest(&data)
+                        || (data.nest.is_none()
+                            && !Self::can_see_target(data.viewshed, data.player_point))
+                    {
+                        data.state.status = Behavior::Rest;
+                    } else if let Some(intent) = Self::flee(&mut data) {
+                        return intent;
+                    } else if Self::can_see_target(data.viewshed, data.player_point) {
+                        // cornered, so turn and fight
+                        match Self::choose_attack(&mut data) {
+                            Some(info) => {
+                                data.state.status = Behavior::AttackStartup {
+                                    turns_left: crate::attack_type::get_startup(info.attack_type),
+                                    info,
+                                };
+                            }
+                            None => return Self::move_towards(data.player_point, data),
+                        }
+                    } else {
+                        return NextIntent::None;
+                    }
                 }
+                Behavior::Rest => {
+                    if Self::can_see_target(data.viewshed, data.player_point) {
+                        data.state.status = Behavior::Chase {
+                            target_point: data.player_point,
+                        };
+                    } else {
+                        if let Some(health) = data.health {
+                            health.current = std::cmp::min(health.max, health.current + REST_HEAL);
+
+                            if health.current >= health.max {
+                                data.state.status = Behavior::Wander;
+                            }
+                        }
+
+                        return NextIntent::None;
+                    }
+                }
+            }
+        }
+    }
+
+    // roll among the attacks that can actually hit the player from here
+    fn choose_attack(data: &mut AiStepData) -> Option<AttackInfo> {
+        let orig_point = data.pos.as_point();
+        let valid_attacks = data
+            .moveset
+            .moves
+            .iter()
+            .filter_map(|(attack_type, chance)| {
+                crate::attack_type::is_attack_valid(*attack_type, orig_point, data.player_point)
+                    .map(|attack_loc| (*attack_type, attack_loc, *chance))
+            })
+            .collect::<Vec<_>>();
+
+        let total_prob: f32 = valid_attacks.iter().map(|(_, _, chance)| chance).sum();
+        let rolled_prob = data.rng.rand::<f32>() * total_prob;
+        let mut cumul_prob: f32 = 0.0;
+
+        for (attack_type, attack_loc, chance) in valid_attacks.iter() {
+            cumul_prob += chance;
+            if rolled_prob <= cumul_prob {
+                return Some(AttackInfo {
+                    attack_type: *attack_type,
+                    attack_loc: *attack_loc,
+                });
             }
         }
+
+        // rounding can leave the roll just past the end
+        valid_attacks
+            .last()
+            .map(|(attack_type, attack_loc, _)| AttackInfo {
+                attack_type: *attack_type,
+                attack_loc: *attack_loc,
+            })
+    }
+
+    // how good a spot is for using an attack, preferring to be at the edge of its reach
+    fn position_score(
+        attack_type: crate::AttackType,
+        from: rltk::Point,
+        target: rltk::Point,
+    ) -> Option<i32> {
+        crate::attack_type::is_attack_valid(attack_type, from, target).map(|_| {
+            let dist = std::cmp::max((from.x - target.x).abs(), (from.y - target.y).abs());
+            -(dist - crate::attack_type::get_attack_reach(attack_type)).abs()
+        })
+    }
+
+    // step somewhere our most likely attack works better, so ranged monsters keep their distance
+    fn reposition(data: &mut AiStepData) -> Option<NextIntent> {
+        let (preferred, _) = data
+            .moveset
+            .moves
+            .iter()
+            .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())?;
+
+        let curr_index = data.map.get_index(data.pos.x, data.pos.y);
+        let curr_score = Self::position_score(*preferred, data.pos.as_point(), data.player_point);
+
+        let exits = crate::flow_field::get_exits(data.map, data.ent, &data.footprint, curr_index);
+
+        exits
+            .iter()
+            .map(|index| data.map.index_to_point2d(*index))
+            .filter_map(|point| {
+                Self::position_score(*preferred, point, data.player_point)
+                    .map(|score| (point, score))
+            })
+            .filter(|(_, score)| Some(*score) > curr_score)
+            .max_by_key(|(_, score)| *score)
+            .map(|(point, _)| NextIntent::Move {
+                intent: MoveIntent {
+                    loc: point,
+                    force_facing: None,
+                },
+            })
     }
 
     fn move_random(data: AiStepData) -> NextIntent {
         let curr_index = data.map.get_index(data.pos.x, data.pos.y);
 
         // pick a random tile we can move to
-        let exits = data
-            .map
-            .get_available_exits_for(curr_index, data.ent, data.multi);
+        let exits = crate::flow_field::get_exits(data.map, data.ent, &data.footprint, curr_index);
         if exits.len() > 0 {
             let exit_index = data.rng.range(0, exits.len());
-            let chosen_exit = exits[exit_index].0;
+            let chosen_exit = exits[exit_index];
             return NextIntent::Move {
                 intent: MoveIntent {
                     loc: data.map.index_to_point2d(chosen_exit),
@@ -267,66 +478,322 @@ impl AiSystem {
                 },
             };
         } else {
-            // TODO: help we're stuck
+            // nowhere to go counts as a failed move
+            if let Some(stuck) = data.stuck {
+                stuck.failed_moves += 1;
+                stuck.clear_moves = 0;
+                stuck.blocked_dir = None;
+            }
+
             return NextIntent::None;
         }
     }
 
-    fn move_towards(target_point: rltk::Point, data: AiStepData) -> NextIntent {
-        let curr_index = data.map.get_index(data.pos.x, data.pos.y);
-        let target_index = data.map.point2d_to_index(target_point);
-        let path = Self::get_path_to(data.ent, data.map, curr_index, target_index, data.multi);
+    // try more and more drastic ways of getting out of a tight spot
+    // backing up, then sidestepping, then pulling parts in, then just showing up somewhere else
+    fn unstick(data: &mut AiStepData) -> Option<NextIntent> {
+        let stuck = data.stuck.as_deref_mut()?;
+        let curr_point = data.pos.as_point();
+        let facing = data.facing;
 
-        match path {
-            None => {
-                if let Some(path) = &data.state.prev_path {
-                    if data.state.path_step < path.steps.len() {
-                        let next_pos = data.map.index_to_point2d(path.steps[data.state.path_step]);
-                        data.state.path_step += 1;
+        if stuck.clear_moves >= STUCK_RESET_MOVES {
+            stuck.recoveries = 0;
+        }
 
-                        return NextIntent::Move {
-                            intent: MoveIntent {
-                                loc: next_pos,
-                                force_facing: None,
-                            },
-                        };
-                    }
-                }
+        // spread back out once we've made it through
+        if stuck.failed_moves == 0 {
+            if stuck.clear_moves < UNSQUEEZE_MOVES {
+                return None;
+            }
+
+            let squeezed = stuck.squeezed.take()?;
+            let multi = data.multi?;
+            let part_delta: Vec<rltk::Point> = squeezed
+                .iter()
+                .map(|delta| rltk::Point::zero() - *delta)
+                .collect();
+            let restored = Self::shift_parts(multi, &part_delta, facing)?;
+
+            if crate::flow_field::can_stand(
+                data.map,
+                data.ent,
+                &crate::flow_field::get_footprint(Some(&restored)),
+                curr_point,
+            ) {
+                return Some(NextIntent::PartMove {
+                    intent: crate::PartMoveIntent { part_delta },
+                });
+            }
+
+            stuck.squeezed = Some(squeezed);
+            return None;
+        }
+
+        if stuck.failed_moves < STUCK_THRESHOLD {
+            return None;
+        }
+
+        stuck.recoveries += 1;
+
+        if let Some(blocked) = stuck.blocked_dir {
+            let reverse = rltk::Point::zero() - blocked;
+            let sideways = [
+                rltk::Point::new(-blocked.y, blocked.x),
+                rltk::Point::new(blocked.y, -blocked.x),
+            ];
 
-                // no path to target, attempt to move towards the target
-                let curr_point = data.pos.as_point();
-                let dir = crate::Direction::get_direction_towards(curr_point, target_point)
-                    .unwrap_or(crate::Direction::N);
-                let next_point = crate::Direction::point_in_direction(curr_point, dir);
-
-                if data
-                    .map
-                    .is_exit_valid_for(next_point.x, next_point.y, data.ent, data.multi)
-                {
-                    return NextIntent::Move {
+            let mut candidates = Vec::new();
+            if stuck.recoveries <= 1 {
+                candidates.push(reverse);
+            }
+            if stuck.recoveries <= 2 {
+                candidates.extend(sideways);
+            }
+
+            for delta in candidates {
+                let next_point = curr_point + delta;
+                if crate::flow_field::can_stand(data.map, data.ent, &data.footprint, next_point) {
+                    return Some(NextIntent::Move {
                         intent: MoveIntent {
                             loc: next_point,
                             force_facing: None,
                         },
-                    };
+                    });
                 }
+            }
+        }
+
+        if stuck.recoveries <= 3 && stuck.squeezed.is_none() {
+            if let Some(multi) = data.multi {
+                // pull parts in across the direction we're trying to go
+                let part_delta: Vec<rltk::Point> = multi
+                    .part_list
+                    .iter()
+                    .map(|part| {
+                        let sum = part
+                            .symbol_map
+                            .keys()
+                            .fold(rltk::Point::zero(), |acc, pos| acc + *pos);
+                        let mut delta = rltk::Point::new(-sum.x.signum(), -sum.y.signum());
+
+                        if let Some(blocked) = stuck.blocked_dir {
+                            if blocked.x != 0 {
+                                delta.x = 0;
+                            }
+                            if blocked.y != 0 {
+                                delta.y = 0;
+                            }
+                        }
+
+                        facing.map_or(delta, |facing| facing.unrotate_point(delta))
+                    })
+                    .collect();
 
-                // can't move towards the target, just make a random move
-                return Self::move_random(data);
+                if part_delta.iter().any(|delta| *delta != rltk::Point::zero()) {
+                    if let Some(squeezed) = Self::shift_parts(multi, &part_delta, facing) {
+                        if crate::flow_field::can_stand(
+                            data.map,
+                            data.ent,
+                            &crate::flow_field::get_footprint(Some(&squeezed)),
+                            curr_point,
+                        ) {
+                            stuck.squeezed = Some(part_delta.clone());
+                            stuck.failed_moves = 0;
+                            stuck.clear_moves = 0;
+
+                            return Some(NextIntent::PartMove {
+                                intent: crate::PartMoveIntent { part_delta },
+                            });
+                        }
+                    }
+                }
             }
-            Some(path) => {
-                let next_pos = data.map.index_to_point2d(path.steps[1]);
-                data.state.prev_path = Some(path);
-                data.state.path_step = 2;
-
-                return NextIntent::Move {
-                    intent: MoveIntent {
-                        loc: next_pos,
-                        force_facing: None,
-                    },
-                };
+        }
+
+        // last resort, move to the closest spot we fit in
+        for range in 1..=RELOCATE_RANGE {
+            for dx in -range..=range {
+                for dy in -range..=range {
+                    if dx.abs() != range && dy.abs() != range {
+                        continue;
+                    }
+
+                    let next_point = curr_point + rltk::Point::new(dx, dy);
+                    if crate::flow_field::can_stand(data.map, data.ent, &data.footprint, next_point)
+                    {
+                        stuck.recoveries = 0;
+
+                        return Some(NextIntent::Move {
+                            intent: MoveIntent {
+                                loc: next_point,
+                                force_facing: None,
+                            },
+                        });
+                    }
+                }
             }
         }
+
+        None
+    }
+
+    // what a body would look like after a part movement, or None if the movement doesn't match it
+    fn shift_parts(
+        multi: &crate::MultiTile,
+        part_delta: &[rltk::Point],
+        facing: Option<crate::Direction>,
+    ) -> Option<crate::MultiTile> {
+        if part_delta.len() != multi.part_list.len() {
+            return None;
+        }
+
+        let mut part_list = multi.part_list.clone();
+        for (part, delta) in part_list.iter_mut().zip(part_delta.iter()) {
+            let delta = facing.map_or(*delta, |facing| facing.rotate_point(*delta));
+            part.symbol_map = part
+                .symbol_map
+                .iter()
+                .map(|(pos, symbol)| (*pos + delta, *symbol))
+                .collect();
+        }
+
+        Some(crate::MultiTile {
+            bounds: crate::all_bounds(&part_list),
+            part_list,
+        })
+    }
+
+    fn move_towards(target_point: rltk::Point, data: AiStepData) -> NextIntent {
+        let curr_index = data.map.get_index(data.pos.x, data.pos.y);
+        let target_index = data.map.point2d_to_index(target_point);
+        let field = data
+            .flow_fields
+            .get(data.map, target_index, &data.footprint);
+
+        // follow the flow field downhill
+        let best_exit =
+            crate::flow_field::get_exits(data.map, data.ent, &data.footprint, curr_index)
+                .into_iter()
+                .filter(|index| field[*index] < field[curr_index])
+                .min_by(|a, b| field[*a].partial_cmp(&field[*b]).unwrap());
+
+        if let Some(index) = best_exit {
+            return NextIntent::Move {
+                intent: MoveIntent {
+                    loc: data.map.index_to_point2d(index),
+                    force_facing: None,
+                },
+            };
+        }
+
+        // no path to target, attempt to move towards the target
+        let curr_point = data.pos.as_point();
+        let dir = crate::Direction::get_direction_towards(curr_point, target_point)
+            .unwrap_or(crate::Direction::N);
+        let next_point = crate::Direction::point_in_direction(curr_point, dir);
+
+        if crate::flow_field::can_stand(data.map, data.ent, &data.footprint, next_point) {
+            return NextIntent::Move {
+                intent: MoveIntent {
+                    loc: next_point,
+                    force_facing: None,
+                },
+            };
+        }
+
+        // can't move towards the target, just make a random move
+        Self::move_random(data)
+    }
+
+    // hurt monsters run unless they're already home
+    fn should_flee(data: &AiStepData) -> bool {
+        let hurt = data
+            .health
+            .as_ref()
+            .is_some_and(|health| health.current * 100 <= health.max * FLEE_HEALTH_PERCENT);
+
+        hurt && data.nest.is_some() && !Self::at_nest(data)
+    }
+
+    fn at_nest(data: &AiStepData) -> bool {
+        data.nest.is_some_and(|nest| {
+            DistanceAlg::Pythagoras.distance2d(data.pos.as_point(), nest) <= NEST_RADIUS
+        })
+    }
+
+    // step towards the nest while keeping away from the player
+    fn flee(data: &mut AiStepData) -> Option<NextIntent> {
+        let curr_index = data.map.get_index(data.pos.x, data.pos.y);
+        let player_index = data.map.point2d_to_index(data.player_point);
+
+        let player_field = data
+            .flow_fields
+            .get(data.map, player_index, &data.footprint);
+        let nest_field = data.nest.map(|nest| {
+            let nest_index = data.map.point2d_to_index(nest);
+            data.flow_fields.get(data.map, nest_index, &data.footprint)
+        });
+
+        let safety = |index: usize| {
+            let to_nest = nest_field.as_ref().map_or(0.0, |field| field[index]);
+            let from_player = player_field[index].min(FLEE_SAFE_DISTANCE);
+            to_nest - FLEE_WEIGHT * from_player
+        };
+
+        let best_exit =
+            crate::flow_field::get_exits(data.map, data.ent, &data.footprint, curr_index)
+                .into_iter()
+                .filter(|index| safety(*index) < safety(curr_index))
+                .min_by(|a, b| safety(*a).partial_cmp(&safety(*b)).unwrap());
+
+        // None when cornered, with nowhere safer to go
+        best_exit.map(|index| NextIntent::Move {
+            intent: MoveIntent {
+                loc: data.map.index_to_point2d(index),
+                force_facing: None,
+            },
+        })
+    }
+
+    // where a pack member should be while the rest of the pack gets into position
+    // returns None once it's time to attack
+    fn flank_point(data: &mut AiStepData) -> Option<rltk::Point> {
+        let member = data.pack.as_deref_mut()?;
+        let curr_point = data.pos.as_point();
+
+        if data.pack_ready || member.wait >= PACK_MAX_WAIT {
+            member.wait = 0;
+            return None;
+        }
+
+        // already next to the player, hold here
+        if Self::next_to(curr_point, data.player_point) {
+            member.wait += 1;
+            return Some(curr_point);
+        }
+
+        let (dx, dy) = FLANK_OFFSETS[member.slot % FLANK_OFFSETS.len()];
+        let slot_point = data.player_point + rltk::Point::new(dx, dy);
+
+        // take the closest open spot around the player to our own
+        // and if the player is surrounded, keep closing in anyway
+        let open_point = FLANK_OFFSETS
+            .iter()
+            .map(|(dx, dy)| data.player_point + rltk::Point::new(*dx, *dy))
+            .filter(|point| {
+                crate::flow_field::can_stand(data.map, data.ent, &data.footprint, *point)
+            })
+            .min_by(|a, b| {
+                let dist_a = DistanceAlg::Pythagoras.distance2d(*a, slot_point);
+                let dist_b = DistanceAlg::Pythagoras.distance2d(*b, slot_point);
+                dist_a.partial_cmp(&dist_b).unwrap()
+            });
+
+        Some(open_point.unwrap_or(data.player_point))
+    }
+
+    fn next_to(a: rltk::Point, b: rltk::Point) -> bool {
+        (a.x - b.x).abs() <= 1 && (a.y - b.y).abs() <= 1
     }
 
     fn can_see_target(viewshed: &crate::Viewshed, target: rltk::Point) -> bool {
@@ -335,22 +802,445 @@ impl AiSystem {
             .iter()
             .any(|pos| pos.x == target.x && pos.y == target.y)
     }
+}
 
-    fn get_path_to(
-        entity: Entity,
-        map: &mut Map,
-        curr_index: usize,
-        target_index: usize,
-        multi_component: Option<&crate::MultiTile>,
-    ) -> Option<rltk::NavigationPath> {
-        map.set_additional_args(entity, multi_component);
-        let path = rltk::a_star_search(curr_index, target_index, &*map);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rltk::Point;
 
-        if path.success && path.steps.len() > 1 {
-            return Some(path);
-        } else {
-            println!("No path exists!");
-            return None;
+    fn build_world() -> World {
+        let mut world = crate::test_util::build_world(20, 12, Point::new(5, 5));
+        System::setup(&mut AiSystem, &mut world);
+        world
+    }
+
+    // a monster that can see the whole test map
+    fn spawn(world: &mut World, point: Point, current: i32, nest: Option<Point>) -> Entity {
+        let mut visible = Vec::new();
+        for x in 0..20 {
+            for y in 0..12 {
+                visible.push(Point::new(x, y));
+            }
+        }
+
+        let mut builder = world
+            .create_entity()
+            .with(crate::Position {
+                x: point.x,
+                y: point.y,
+            })
+            .with(crate::Health { current, max: 10 })
+            .with(crate::AiState {
+                status: Behavior::Wander,
+            })
+            .with(crate::Viewshed {
+                visible,
+                dirty: false,
+                range: 30,
+            })
+            .with(crate::Moveset {
+                moves: vec![],
+                bump_attack: crate::AttackType::Punch,
+            });
+
+        if let Some(point) = nest {
+            builder = builder.with(crate::Nest { point });
+        }
+
+        let ent = builder.build();
+        let mut map = world.fetch_mut::<crate::Map>();
+        let index = map.get_index(point.x, point.y);
+        map.track_creature(ent, index, None);
+
+        ent
+    }
+
+    fn take_turn(world: &mut World, ent: Entity) -> Option<Point> {
+        world
+            .write_storage::<crate::CanActFlag>()
+            .insert(
+                ent,
+                crate::CanActFlag {
+                    is_reaction: false,
+                    reaction_target: None,
+                },
+            )
+            .expect("Failed to insert CanActFlag");
+
+        AiSystem.run_now(world);
+        world.maintain();
+
+        world
+            .write_storage::<crate::MoveIntent>()
+            .remove(ent)
+            .map(|intent| intent.loc)
+    }
+
+    fn is_fleeing(world: &World, ent: Entity) -> bool {
+        let states = world.read_storage::<crate::AiState>();
+        matches!(states.get(ent).unwrap().status, Behavior::Flee)
+    }
+
+    #[test]
+    fn hurt_monsters_flee_towards_their_nest() {
+        let mut world = build_world();
+        let ent = spawn(&mut world, Point::new(8, 5), 2, Some(Point::new(16, 5)));
+
+        assert_eq!(take_turn(&mut world, ent), Some(Point::new(9, 5)));
+        assert!(is_fleeing(&world, ent));
+    }
+
+    #[test]
+    fn healthy_monsters_do_not_flee() {
+        let mut world = build_world();
+        let ent = spawn(&mut world, Point::new(8, 5), 10, Some(Point::new(16, 5)));
+
+        take_turn(&mut world, ent);
+        assert!(!is_fleeing(&world, ent));
+    }
+
+    #[test]
+    fn monsters_rest_at_their_nest() {
+        let mut world = build_world();
+        let ent = spawn(&mut world, Point::new(16, 5), 2, Some(Point::new(16, 5)));
+        world
+            .write_storage::<crate::AiState>()
+            .get_mut(ent)
+            .unwrap()
+            .status = Behavior::Flee;
+
+        // the player is out of sight
+        world
+            .write_storage::<crate::Viewshed>()
+            .get_mut(ent)
+            .unwrap()
+            .visible
+            .clear();
+
+        assert_eq!(take_turn(&mut world, ent), None);
+        assert!(matches!(
+            world
+                .read_storage::<crate::AiState>()
+                .get(ent)
+                .unwrap()
+                .status,
+            Behavior::Rest
+        ));
+        assert_eq!(
+            world
+                .read_storage::<crate::Health>()
+                .get(ent)
+                .unwrap()
+                .current,
+            3
+        );
+    }
+
+    // a dead end running west from the player at (5, 5), with the nest on the other side
+    // the player stands in the way out
+    fn dead_end(world: &mut World, length: i32) {
+        let player = *world.fetch::<Entity>();
+        let mut map = world.fetch_mut::<crate::Map>();
+        for x in 5 - length..5 {
+            for y in [4, 6] {
+                let index = map.get_index(x, y);
+                map.tiles[index] = crate::TileType::Wall;
+            }
+        }
+        let index = map.get_index(4 - length, 5);
+        map.tiles[index] = crate::TileType::Wall;
+        map.set_blocked_tiles();
+
+        let index = map.get_index(5, 5);
+        map.track_creature(player, index, None);
+    }
+
+    #[test]
+    fn cornered_monsters_fight_back() {
+        let mut world = build_world();
+        dead_end(&mut world, 1);
+        let ent = spawn(&mut world, Point::new(4, 5), 2, Some(Point::new(16, 5)));
+        set_moves(&mut world, ent, vec![(crate::AttackType::Punch, 1.0)]);
+
+        assert_eq!(take_turn(&mut world, ent), None);
+        assert_eq!(chosen_attack(&world, ent), Some(crate::AttackType::Punch));
+    }
+
+    #[test]
+    fn cornered_monsters_close_in_when_out_of_reach() {
+        let mut world = build_world();
+        dead_end(&mut world, 2);
+        let ent = spawn(&mut world, Point::new(3, 5), 2, Some(Point::new(16, 5)));
+        set_moves(&mut world, ent, vec![(crate::AttackType::Punch, 1.0)]);
+
+        assert_eq!(take_turn(&mut world, ent), Some(Point::new(4, 5)));
+        assert!(is_fleeing(&world, ent));
+    }
+
+    fn set_moves(world: &mut World, ent: Entity, moves: Vec<(crate::AttackType, f32)>) {
+        world
+            .write_storage::<crate::Moveset>()
+            .get_mut(ent)
+            .unwrap()
+            .moves = moves;
+    }
+
+    fn chosen_attack(world: &World, ent: Entity) -> Option<crate::AttackType> {
+        match world
+            .read_storage::<crate::AiState>()
+            .get(ent)
+            .unwrap()
+            .status
+        {
+            Behavior::AttackStartup { info, .. }
+            | Behavior::Attack { info }
+            | Behavior::AttackRecovery { info, .. } => Some(info.attack_type),
+            _ => None,
         }
     }
+
+    #[test]
+    fn only_attacks_in_range_are_chosen() {
+        let mut world = build_world();
+        let ent = spawn(&mut world, Point::new(8, 5), 10, None);
+        set_moves(
+            &mut world,
+            ent,
+            vec![
+                (crate::AttackType::Punch, 0.9),
+                (crate::AttackType::Ranged, 0.1),
+            ],
+        );
+
+        assert_eq!(take_turn(&mut world, ent), None);
+        assert_eq!(chosen_attack(&world, ent), Some(crate::AttackType::Ranged));
+    }
+
+    #[test]
+    fn ranged_monsters_back_away_to_shoot() {
+        let mut world = build_world();
+        let ent = spawn(&mut world, Point::new(6, 5), 10, None);
+        set_moves(
+            &mut world,
+            ent,
+            vec![
+                (crate::AttackType::Punch, 0.25),
+                (crate::AttackType::Ranged, 0.75),
+            ],
+        );
+
+        let next = take_turn(&mut world, ent).expect("monster should move");
+        assert_eq!(next.x, 7);
+        assert!(chosen_attack(&world, ent).is_none());
+    }
+
+    fn set_stuck(world: &mut World, ent: Entity, stuck: crate::StuckInfo) {
+        world
+            .write_storage::<crate::StuckInfo>()
+            .insert(ent, stuck)
+            .expect("Failed to insert StuckInfo");
+    }
+
+    fn part_move(world: &mut World, ent: Entity) -> Option<Vec<Point>> {
+        world
+            .write_storage::<crate::PartMoveIntent>()
+            .remove(ent)
+            .map(|intent| intent.part_delta)
+    }
+
+    #[test]
+    fn stuck_monsters_back_up() {
+        let mut world = build_world();
+        let ent = spawn(&mut world, Point::new(8, 5), 10, None);
+        set_stuck(
+            &mut world,
+            ent,
+            crate::StuckInfo {
+                failed_moves: STUCK_THRESHOLD,
+                blocked_dir: Some(Point::new(1, 0)),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(take_turn(&mut world, ent), Some(Point::new(7, 5)));
+    }
+
+    #[test]
+    fn boxed_in_monsters_relocate() {
+        let mut world = build_world();
+        {
+            let mut map = world.fetch_mut::<crate::Map>();
+            for x in 7..=9 {
+                for y in 4..=6 {
+                    if x != 8 || y != 5 {
+                        let index = map.get_index(x, y);
+                        map.tiles[index] = crate::TileType::Wall;
+                    }
+                }
+            }
+            map.set_blocked_tiles();
+        }
+        let ent = spawn(&mut world, Point::new(8, 5), 10, None);
+        set_stuck(&mut world, ent, crate::StuckInfo::default());
+        world
+            .write_storage::<crate::Viewshed>()
+            .get_mut(ent)
+            .unwrap()
+            .visible
+            .clear();
+
+        for _ in 0..STUCK_THRESHOLD {
+            assert_eq!(take_turn(&mut world, ent), None);
+        }
+
+        let next = take_turn(&mut world, ent).expect("monster should relocate");
+        assert_eq!(std::cmp::max((next.x - 8).abs(), (next.y - 5).abs()), 2);
+    }
+
+    #[test]
+    fn wide_monsters_squeeze_and_spread_out() {
+        let mut world = build_world();
+        let ent = spawn(&mut world, Point::new(8, 5), 10, None);
+        let part_list = [-1, 1]
+            .iter()
+            .map(|x| crate::MonsterPart {
+                symbol_map: std::collections::HashMap::from([(
+                    Point::new(*x, 0),
+                    rltk::to_cp437('-'),
+                )]),
+                health: 2,
+                max_health: 2,
+                kind: crate::PartKind::Body,
+                hitzone: crate::Hitzone::default(),
+                discovered: vec![],
+                on_break: vec![],
+                broken: false,
+            })
+            .collect::<Vec<_>>();
+        world
+            .write_storage::<crate::MultiTile>()
+            .insert(
+                ent,
+                crate::MultiTile {
+                    bounds: crate::all_bounds(&part_list),
+                    part_list,
+                },
+            )
+            .expect("Failed to insert MultiTile");
+        set_stuck(
+            &mut world,
+            ent,
+            crate::StuckInfo {
+                failed_moves: STUCK_THRESHOLD,
+                recoveries: 2,
+                blocked_dir: Some(Point::new(0, -1)),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(take_turn(&mut world, ent), None);
+        assert_eq!(
+            part_move(&mut world, ent),
+            Some(vec![Point::new(1, 0), Point::new(-1, 0)])
+        );
+
+        // after making it through, the parts go back where they were
+        world
+            .write_storage::<crate::StuckInfo>()
+            .get_mut(ent)
+            .unwrap()
+            .clear_moves = UNSQUEEZE_MOVES;
+
+        assert_eq!(take_turn(&mut world, ent), None);
+        assert_eq!(
+            part_move(&mut world, ent),
+            Some(vec![Point::new(-1, 0), Point::new(1, 0)])
+        );
+        assert!(world
+            .read_storage::<crate::StuckInfo>()
+            .get(ent)
+            .unwrap()
+            .squeezed
+            .is_none());
+    }
+
+    fn join_pack(world: &mut World, ent: Entity, leader: Entity, slot: usize) {
+        world
+            .write_storage::<crate::PackMember>()
+            .insert(
+                ent,
+                crate::PackMember {
+                    leader,
+                    slot,
+                    wait: 0,
+                },
+            )
+            .expect("Failed to insert PackMember");
+        set_moves(world, ent, vec![(crate::AttackType::Punch, 1.0)]);
+    }
+
+    #[test]
+    fn packs_wait_for_flankers() {
+        let mut world = build_world();
+        let leader = spawn(&mut world, Point::new(6, 5), 10, None);
+        let follower = spawn(&mut world, Point::new(12, 5), 10, None);
+        join_pack(&mut world, leader, leader, 0);
+        join_pack(&mut world, follower, leader, 1);
+
+        // the leader is in reach but holds off until the follower gets around the other side
+        assert_eq!(take_turn(&mut world, leader), None);
+        assert_eq!(chosen_attack(&world, leader), None);
+
+        // heading for the flank at (5, 6) rather than straight at the player
+        assert_eq!(take_turn(&mut world, follower), Some(Point::new(11, 5)));
+        world
+            .write_storage::<crate::Position>()
+            .insert(follower, crate::Position { x: 5, y: 7 })
+            .expect("Failed to move follower");
+        assert_eq!(take_turn(&mut world, follower), Some(Point::new(5, 6)));
+
+        // once the follower is in place, the leader attacks
+        world
+            .write_storage::<crate::Position>()
+            .insert(follower, crate::Position { x: 5, y: 6 })
+            .expect("Failed to move follower");
+        take_turn(&mut world, leader);
+        assert_eq!(
+            chosen_attack(&world, leader),
+            Some(crate::AttackType::Punch)
+        );
+    }
+
+    #[test]
+    fn packs_flank_around_blocked_spots() {
+        let mut world = build_world();
+        let leader = spawn(&mut world, Point::new(6, 5), 10, None);
+        let follower = spawn(&mut world, Point::new(6, 8), 10, None);
+        spawn(&mut world, Point::new(5, 6), 10, None);
+        join_pack(&mut world, leader, leader, 0);
+        join_pack(&mut world, follower, leader, 1);
+
+        // the flank below the player is taken, so the follower goes for the spot beside it at (6, 6)
+        assert_eq!(take_turn(&mut world, follower), Some(Point::new(6, 7)));
+        assert_eq!(chosen_attack(&world, follower), None);
+    }
+
+    #[test]
+    fn packs_retreat_without_a_leader() {
+        let mut world = build_world();
+        let leader = spawn(&mut world, Point::new(6, 5), 10, None);
+        let follower = spawn(&mut world, Point::new(8, 5), 10, Some(Point::new(16, 5)));
+        join_pack(&mut world, leader, leader, 0);
+        join_pack(&mut world, follower, leader, 1);
+
+        world
+            .delete_entity(leader)
+            .expect("Failed to delete leader");
+        world.maintain();
+
+        assert_eq!(take_turn(&mut world, follower), Some(Point::new(9, 5)));
+        assert!(is_fleeing(&world, follower));
+        assert!(!world.read_storage::<crate::PackMember>().contains(follower));
+    }
 }
@@ -1,10 +1,17 @@
 use crate::attack_type;
+use crate::status::{self, StatusKind};
 use rltk::Algorithm2D;
 use specs::prelude::*;
 use std::collections::HashMap;
 
 pub struct AttackSystem;
 
+const BLOCK_STAM_CHIP: i32 = 1;
+const HEAD_STUN_MULT: i32 = 3;
+const STUN_THRESHOLD_GROWTH: i32 = 5;
+const STUN_DURATION: u32 = 3;
+const SNEAK_ATTACK_MULT: i32 = 2;
+
 impl<'a> System<'a> for AttackSystem {
     type SystemData = (
         Entities<'a>,
@@ -17,9 +24,19 @@ impl<'a> System<'a> for AttackSystem {
         WriteExpect<'a, crate::ParticleBuilder>,
         ReadExpect<'a, Entity>,
         WriteExpect<'a, crate::RunState>,
-        WriteStorage<'a, crate::Invulnerable>,
         WriteStorage<'a, crate::MoveIntent>,
         WriteStorage<'a, crate::Stamina>,
+        WriteStorage<'a, crate::BlockAttack>,
+        WriteStorage<'a, crate::PushForce>,
+        WriteStorage<'a, crate::HitFlag>,
+        ReadStorage<'a, crate::Projectile>,
+        WriteStorage<'a, crate::StunMeter>,
+        WriteStorage<'a, crate::AiState>,
+        ReadStorage<'a, crate::StatBonus>,
+        WriteStorage<'a, crate::StatusEffects>,
+        WriteExpect<'a, crate::gamelog::GameLog>,
+        Write<'a, crate::sys_awareness::NoiseQueue>,
+        Read<'a, LazyUpdate>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
@@ -34,11 +51,22 @@ impl<'a> System<'a> for AttackSystem {
             mut p_builder,
             player,
             mut run_state,
-            mut invulns,
             mut movements,
             mut stams,
+            mut blocks,
+            mut pushes,
+            mut hit_flags,
+            projectiles,
+            mut stun_meters,
+            mut ai_states,
+            stat_bonuses,
+            mut statuses,
+            mut log,
+            mut noise_queue,
+            lazy,
         ) = data;
         let mut finished_attacks = Vec::new();
+        let mut incapacitated = Vec::new();
 
         for (ent, intent) in (&entities, &mut attacks).join() {
             if intent.delay > 0 {
@@ -50,53 +78,143 @@ impl<'a> System<'a> for AttackSystem {
             }
 
             finished_attacks.push(ent);
+
+            // monsters hear the player fighting
+            if ent == *player {
+                if let Some(pos) = positions.get(ent) {
+                    noise_queue.noises.push(crate::sys_awareness::Noise {
+                        point: pos.as_point(),
+                        radius: attack_type::get_noise(intent.main),
+                    });
+                }
+            }
+
             let trait_list = attack_type::get_attack_traits(intent.main);
 
             for att_trait in trait_list {
                 match att_trait {
-                    crate::AttackTrait::Knockback { amount: _ } => {
-                        //
+                    crate::AttackTrait::Knockback { amount } => {
+                        let attacker_point = match positions.get(ent) {
+                            Some(pos) => pos.as_point(),
+                            None => continue,
+                        };
+
+                        for ent_hit in get_unguarded_targets(&map, intent, ent, &statuses) {
+                            let target_point = match positions.get(ent_hit) {
+                                Some(pos) => pos.as_point(),
+                                None => continue,
+                            };
+
+                            // targets are pushed directly away from the attacker
+                            if let Some(dir) = crate::Direction::get_direction_towards(
+                                attacker_point,
+                                target_point,
+                            ) {
+                                pushes
+                                    .insert(
+                                        ent_hit,
+                                        crate::PushForce {
+                                            delta: dir.to_point() * amount,
+                                        },
+                                    )
+                                    .expect("Failed to insert knockback");
+                            }
+                        }
                     }
                     crate::AttackTrait::Damage { amount } => {
-                        let targets = attack_type::each_attack_target(intent.main, intent.loc);
-                        let mut ents_hit = HashMap::new();
-
-                        for point in targets {
+                        for point in attack_type::each_attack_target(intent.main, intent.loc) {
                             p_builder.make_bg_particle(point);
-                            let point_index = map.point2d_to_index(point);
-                            if let Some(aff_ent) = map.creature_map.get(&point_index) {
-                                // avoid self damage
-                                if *aff_ent == ent {
-                                    continue;
-                                }
+                        }
+
+                        let damage_kind = attack_type::get_damage_kind(intent.main);
+                        let mut amount = amount;
+                        if amount > 0 {
+                            if let Some(bonus) = stat_bonuses.get(ent) {
+                                amount += bonus.attack;
+                            }
 
-                                let hit_locs = ents_hit.entry(aff_ent).or_insert(Vec::new());
-                                hit_locs.push(point);
+                            if status::has_status(&statuses, ent, StatusKind::Sharpened) {
+                                amount += 1;
                             }
                         }
 
-                        for (ent_hit, hit_locs) in ents_hit {
-                            if invulns.get(*ent_hit).is_some() {
+                        for (ent_hit, hit_locs) in get_hit_locations(&map, intent, ent) {
+                            if status::has_status(&statuses, ent_hit, StatusKind::Invulnerable) {
                                 continue;
                             }
 
-                            if let Some(mut aff_health) = healths.get_mut(*ent_hit) {
-                                aff_health.current -= amount;
+                            let mut amount = amount;
+                            let asleep =
+                                ai_states.get(ent_hit).is_some_and(|state| {
+                                    matches!(state.status, crate::Behavior::Sleep)
+                                }) || status::has_status(&statuses, ent_hit, StatusKind::Sleep);
+                            if asleep && amount > 0 {
+                                amount *= SNEAK_ATTACK_MULT;
+
+                                if ent == *player {
+                                    log.add("Sneak attack!");
+                                }
+                            }
+
+                            if let Some(block) = blocks.get_mut(ent_hit) {
+                                amount = resolve_block(
+                                    block,
+                                    amount,
+                                    stams.get_mut(ent_hit),
+                                    positions.get(ent),
+                                    positions.get(ent_hit),
+                                    ent_hit,
+                                    &mut pushes,
+                                );
+                            }
+
+                            // armor softens hits, but doesn't stop them completely
+                            if let Some(bonus) = stat_bonuses.get(ent_hit) {
+                                if amount > 0 {
+                                    amount = std::cmp::max(1, amount - bonus.defense);
+                                }
+                            }
 
-                                if let Some(aff_part) = multis.get_mut(*ent_hit) {
-                                    if let Some(pos) = positions.get(*ent_hit) {
-                                        for part in aff_part.part_list.iter_mut() {
-                                            for part_pos in part.symbol_map.keys() {
-                                                let adj_part_pos = pos.as_point() + *part_pos;
-
-                                                if hit_locs.contains(&adj_part_pos) {
-                                                    part.health -= 1;
-                                                    break;
-                                                }
-                                            }
-                                        }
+                            if let (Some(multi), Some(pos)) =
+                                (multis.get_mut(ent_hit), positions.get(ent_hit))
+                            {
+                                let deflected = amount > 0;
+                                amount = apply_hitzones(
+                                    multi,
+                                    pos,
+                                    &hit_locs,
+                                    damage_kind,
+                                    amount,
+                                    ent == *player,
+                                );
+
+                                if deflected && amount == 0 && ent == *player {
+                                    log.add("Your attack bounced off!");
+                                }
+                            }
+
+                            // getting hit wakes up sleeping monsters
+                            if amount > 0 {
+                                if let Some(status) = statuses.get_mut(ent_hit) {
+                                    status.remove(StatusKind::Sleep);
+                                }
+
+                                if let Some(state) = ai_states.get_mut(ent_hit) {
+                                    if matches!(state.status, crate::Behavior::Sleep) {
+                                        state.status = crate::Behavior::Wander;
                                     }
                                 }
+                            }
+
+                            if let Some(mut aff_health) = healths.get_mut(ent_hit) {
+                                aff_health.current -= amount;
+
+                                // let the player's weapon know it got interrupted
+                                if ent_hit == *player && amount > 0 {
+                                    hit_flags
+                                        .insert(ent_hit, crate::HitFlag)
+                                        .expect("Failed to insert HitFlag");
+                                }
 
                                 for pos in hit_locs {
                                     p_builder.make_hit_particle(pos);
@@ -119,31 +237,648 @@ impl<'a> System<'a> for AttackSystem {
                             .ok();
                     }
                     crate::AttackTrait::Invulnerable { duration } => {
-                        invulns
-                            .insert(ent, crate::Invulnerable { duration })
-                            .expect("Failed to make player invulnerable");
+                        let duration = match stat_bonuses.get(ent) {
+                            Some(bonus) => duration + bonus.evade,
+                            None => duration,
+                        };
+
+                        status::inflict(&mut statuses, ent, StatusKind::Invulnerable, 0, duration);
                     }
                     crate::AttackTrait::NeedsStamina { amount } => {
+                        let amount = match stat_bonuses.get(ent) {
+                            Some(bonus) => std::cmp::max(1, amount - bonus.stamina_discount),
+                            None => amount,
+                        };
+
                         if let Some(stamina) = stams.get_mut(ent) {
                             stamina.current -= amount;
                             stamina.recover = false;
                         }
                     }
-                    crate::AttackTrait::Heal { amount: _ } => {
-                        //
+                    crate::AttackTrait::Heal { amount } => {
+                        for ent_healed in get_all_targets(&map, intent) {
+                            if let Some(health) = healths.get_mut(ent_healed) {
+                                health.heal(amount);
+                            }
+                        }
                     }
                     crate::AttackTrait::LanceCharge { dir } => {
                         if ent == *player {
                             *run_state = crate::RunState::Charging { dir, speed: 1 };
                         }
                     }
+                    crate::AttackTrait::Projectile {
+                        dir,
+                        speed,
+                        range,
+                        hit,
+                    } => {
+                        let symbol = match dir {
+                            crate::Direction::N | crate::Direction::S => rltk::to_cp437('|'),
+                            crate::Direction::E | crate::Direction::W => rltk::to_cp437('-'),
+                        };
+
+                        let mut builder = lazy.create_entity(&entities);
+
+                        // arrows carry the shooter's bonuses with them
+                        if let Some(bonus) = stat_bonuses.get(ent) {
+                            builder = builder.with(crate::StatBonus {
+                                attack: bonus.attack,
+                                ..Default::default()
+                            });
+                        }
+
+                        builder
+                            .with(crate::Position {
+                                x: intent.loc.x,
+                                y: intent.loc.y,
+                            })
+                            .with(crate::Renderable {
+                                symbol,
+                                fg: crate::projectile_color(),
+                                bg: crate::bg_color(),
+                            })
+                            .with(crate::Schedulable {
+                                current: 0,
+                                base: 4,
+                                delta: 4,
+                            })
+                            .with(crate::Projectile {
+                                dir,
+                                speed,
+                                range,
+                                source: ent,
+                                hit,
+                            })
+                            .build();
+                    }
+                    crate::AttackTrait::Poison { amount, duration } => {
+                        for ent_hit in get_unguarded_targets(&map, intent, ent, &statuses) {
+                            status::inflict(
+                                &mut statuses,
+                                ent_hit,
+                                StatusKind::Poison,
+                                amount,
+                                duration,
+                            );
+                        }
+                    }
+                    crate::AttackTrait::Bleed { amount, duration } => {
+                        for ent_hit in get_unguarded_targets(&map, intent, ent, &statuses) {
+                            status::inflict(
+                                &mut statuses,
+                                ent_hit,
+                                StatusKind::Bleed,
+                                amount,
+                                duration,
+                            );
+                        }
+                    }
+                    crate::AttackTrait::Paralyze { duration } => {
+                        for ent_hit in get_unguarded_targets(&map, intent, ent, &statuses) {
+                            incapacitated.push((ent_hit, StatusKind::Paralysis, duration));
+                        }
+                    }
+                    crate::AttackTrait::Sleep { duration } => {
+                        for ent_hit in get_unguarded_targets(&map, intent, ent, &statuses) {
+                            incapacitated.push((ent_hit, StatusKind::Sleep, duration));
+                        }
+                    }
+                    crate::AttackTrait::Stun { amount } => {
+                        for (ent_hit, hit_locs) in get_hit_locations(&map, intent, ent) {
+                            if status::has_status(&statuses, ent_hit, StatusKind::Invulnerable) {
+                                continue;
+                            }
+
+                            if let Some(meter) = stun_meters.get_mut(ent_hit) {
+                                // blows to the head build up stun much faster
+                                let head_hit = match (multis.get(ent_hit), positions.get(ent_hit)) {
+                                    (Some(multi), Some(pos)) => is_head_hit(multi, pos, &hit_locs),
+                                    _ => false,
+                                };
+
+                                meter.current += if head_hit {
+                                    amount * HEAD_STUN_MULT
+                                } else {
+                                    amount
+                                };
+
+                                // each stun makes the next one harder to land
+                                if meter.current >= meter.threshold {
+                                    meter.current = 0;
+                                    meter.threshold += STUN_THRESHOLD_GROWTH;
+                                    incapacitated.push((ent_hit, StatusKind::Stun, STUN_DURATION));
+                                }
+                            }
+
+                            for pos in hit_locs {
+                                p_builder.make_hit_particle(pos);
+                            }
+                        }
+                    }
+                    crate::AttackTrait::Daze { turns } => {
+                        for point in attack_type::each_attack_target(intent.main, intent.loc) {
+                            p_builder.make_bg_particle(point);
+                        }
+
+                        for ent_hit in get_unguarded_targets(&map, intent, ent, &statuses) {
+                            if ai_states.contains(ent_hit) {
+                                incapacitated.push((ent_hit, StatusKind::Stun, turns));
+                            }
+                        }
+                    }
                 }
             }
         }
 
+        // monsters that can't act lose the attack they were making
+        // losing turns is handled by the status, only sleep changes what the monster is doing
+        for (ent_hit, kind, duration) in incapacitated {
+            status::inflict(&mut statuses, ent_hit, kind, 0, duration);
+
+            if kind == StatusKind::Sleep {
+                if let Some(state) = ai_states.get_mut(ent_hit) {
+                    state.status = crate::Behavior::Sleep;
+                }
+            }
+
+            attacks.remove(ent_hit);
+            attacks_in_progress.remove(ent_hit);
+        }
+
         for done in finished_attacks.iter() {
             attacks.remove(*done);
             attacks_in_progress.remove(*done);
+
+            // projectiles are used up once they hit something
+            if projectiles.contains(*done) {
+                entities.delete(*done).expect("Failed to remove projectile");
+            }
+        }
+    }
+}
+
+// reduce incoming damage from a guarded attack, returning the damage that gets through
+// a block in the perfect window negates the hit entirely and flags a counter
+fn resolve_block(
+    block: &mut crate::BlockAttack,
+    amount: i32,
+    stamina: Option<&mut crate::Stamina>,
+    attacker_pos: Option<&crate::Position>,
+    target_pos: Option<&crate::Position>,
+    target: Entity,
+    pushes: &mut WriteStorage<crate::PushForce>,
+) -> i32 {
+    if block.perfect_window > 0 {
+        block.countered = true;
+        return 0;
+    }
+
+    // blocking costs stamina, and we can't block without any
+    if let Some(stamina) = stamina {
+        if stamina.current < BLOCK_STAM_CHIP {
+            return amount;
         }
+
+        stamina.current -= BLOCK_STAM_CHIP;
+        stamina.recover = false;
+    }
+
+    // the force of the blow pushes the blocker back
+    if let (Some(attacker_pos), Some(target_pos)) = (attacker_pos, target_pos) {
+        if let Some(dir) =
+            crate::Direction::get_direction_towards(attacker_pos.as_point(), target_pos.as_point())
+        {
+            pushes
+                .insert(
+                    target,
+                    crate::PushForce {
+                        delta: dir.to_point(),
+                    },
+                )
+                .expect("Failed to insert push from blocked attack");
+        }
+    }
+
+    std::cmp::max(0, amount - block.block_amount as i32)
+}
+
+// group the tiles hit by an attack by the entity standing on them, ignoring the attacker
+fn get_hit_locations(
+    map: &crate::Map,
+    intent: &crate::AttackIntent,
+    attacker: Entity,
+) -> HashMap<Entity, Vec<rltk::Point>> {
+    let mut ents_hit = HashMap::new();
+
+    for point in attack_type::each_attack_target(intent.main, intent.loc) {
+        if let Some(aff_ent) = map.creature_map.get(&map.point2d_to_index(point)) {
+            // avoid self damage
+            if *aff_ent == attacker {
+                continue;
+            }
+
+            ents_hit
+                .entry(*aff_ent)
+                .or_insert_with(Vec::new)
+                .push(point);
+        }
+    }
+
+    ents_hit
+}
+
+// damage each part that was hit according to its hitzone, and scale the overall damage by
+// the most favorable hitzone struck, hits to the core of the monster take normal damage
+fn apply_hitzones(
+    multi: &mut crate::MultiTile,
+    pos: &crate::Position,
+    hit_locs: &[rltk::Point],
+    kind: crate::DamageKind,
+    amount: i32,
+    discover: bool,
+) -> i32 {
+    let mut best_mult = None;
+    if hit_locs.contains(&pos.as_point()) {
+        best_mult = Some(100);
+    }
+
+    for part in multi.part_list.iter_mut() {
+        let part_hit = part
+            .symbol_map
+            .keys()
+            .any(|part_pos| hit_locs.contains(&(pos.as_point() + *part_pos)));
+
+        if !part_hit {
+            continue;
+        }
+
+        let mult = part.hitzone.multiplier(kind);
+        best_mult = Some(best_mult.map_or(mult, |best: i32| best.max(mult)));
+
+        if amount > 0 {
+            part.health -= crate::scale_damage(1, mult);
+        }
+
+        if discover && !part.discovered.contains(&kind) {
+            part.discovered.push(kind);
+        }
+    }
+
+    crate::scale_damage(amount, best_mult.unwrap_or(100))
+}
+
+fn is_head_hit(multi: &crate::MultiTile, pos: &crate::Position, hit_locs: &[rltk::Point]) -> bool {
+    multi
+        .part_list
+        .iter()
+        .filter(|part| part.kind == crate::PartKind::Head)
+        .flat_map(|part| part.symbol_map.keys())
+        .any(|part_pos| hit_locs.contains(&(pos.as_point() + *part_pos)))
+}
+
+// every entity caught in the attack, including the attacker
+fn get_all_targets(map: &crate::Map, intent: &crate::AttackIntent) -> Vec<Entity> {
+    let mut targets = Vec::new();
+
+    for point in attack_type::each_attack_target(intent.main, intent.loc) {
+        if let Some(aff_ent) = map.creature_map.get(&map.point2d_to_index(point)) {
+            if !targets.contains(aff_ent) {
+                targets.push(*aff_ent);
+            }
+        }
+    }
+
+    targets
+}
+
+// every entity other than the attacker caught in the attack that isn't invulnerable
+fn get_unguarded_targets(
+    map: &crate::Map,
+    intent: &crate::AttackIntent,
+    attacker: Entity,
+    statuses: &WriteStorage<crate::StatusEffects>,
+) -> Vec<Entity> {
+    let mut targets = Vec::new();
+
+    for point in attack_type::each_attack_target(intent.main, intent.loc) {
+        if let Some(aff_ent) = map.creature_map.get(&map.point2d_to_index(point)) {
+            if *aff_ent != attacker
+                && !status::has_status(statuses, *aff_ent, StatusKind::Invulnerable)
+                && !targets.contains(aff_ent)
+            {
+                targets.push(*aff_ent);
+            }
+        }
+    }
+
+    targets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sys_push::PushSystem;
+
+    fn build_world() -> World {
+        let mut world = crate::test_util::build_world(10, 10, rltk::Point::new(0, 0));
+        System::setup(&mut AttackSystem, &mut world);
+        System::setup(&mut PushSystem, &mut world);
+        world
+    }
+
+    fn spawn_creature(world: &mut World, point: rltk::Point, current: i32, max: i32) -> Entity {
+        let ent = world
+            .create_entity()
+            .with(crate::Position {
+                x: point.x,
+                y: point.y,
+            })
+            .with(crate::Health { current, max })
+            .build();
+
+        let mut map = world.fetch_mut::<crate::Map>();
+        let index = map.point2d_to_index(point);
+        map.creature_map.insert(index, ent);
+
+        ent
+    }
+
+    fn attack(world: &mut World, attacker: Entity, main: crate::AttackType, loc: rltk::Point) {
+        world
+            .write_storage::<crate::AttackIntent>()
+            .insert(
+                attacker,
+                crate::AttackIntent {
+                    main,
+                    modifier: None,
+                    loc,
+                    delay: 0,
+                },
+            )
+            .expect("Failed to insert attack");
+
+        AttackSystem.run_now(world);
+        world.maintain();
+    }
+
+    #[test]
+    fn knockback_pushes_away_from_attacker() {
+        let mut world = build_world();
+        let attacker = spawn_creature(&mut world, rltk::Point::new(5, 5), 10, 10);
+        let target = spawn_creature(&mut world, rltk::Point::new(5, 6), 10, 10);
+
+        attack(
+            &mut world,
+            attacker,
+            crate::AttackType::Push,
+            rltk::Point::new(5, 6),
+        );
+
+        {
+            let pushes = world.read_storage::<crate::PushForce>();
+            assert_eq!(pushes.get(target).unwrap().delta, rltk::Point::new(0, 2));
+            assert!(pushes.get(attacker).is_none());
+        }
+
+        PushSystem.run_now(&world);
+        world.maintain();
+
+        let positions = world.read_storage::<crate::Position>();
+        assert_eq!(
+            positions.get(target).unwrap().as_point(),
+            rltk::Point::new(5, 8)
+        );
+        assert_eq!(
+            positions.get(attacker).unwrap().as_point(),
+            rltk::Point::new(5, 5)
+        );
+    }
+
+    #[test]
+    fn knockback_skips_invulnerable_targets() {
+        let mut world = build_world();
+        let attacker = spawn_creature(&mut world, rltk::Point::new(5, 5), 10, 10);
+        let target = spawn_creature(&mut world, rltk::Point::new(6, 5), 10, 10);
+        status::inflict(
+            &mut world.write_storage::<crate::StatusEffects>(),
+            target,
+            StatusKind::Invulnerable,
+            0,
+            6,
+        );
+
+        attack(
+            &mut world,
+            attacker,
+            crate::AttackType::Push,
+            rltk::Point::new(6, 5),
+        );
+
+        assert!(world
+            .read_storage::<crate::PushForce>()
+            .get(target)
+            .is_none());
+    }
+
+    #[test]
+    fn heal_restores_health() {
+        let mut world = build_world();
+        let healer = spawn_creature(&mut world, rltk::Point::new(5, 5), 3, 10);
+
+        attack(
+            &mut world,
+            healer,
+            crate::AttackType::Recover,
+            rltk::Point::new(5, 5),
+        );
+
+        let healths = world.read_storage::<crate::Health>();
+        assert_eq!(healths.get(healer).unwrap().current, 5);
+    }
+
+    #[test]
+    fn heal_does_not_exceed_max() {
+        let mut world = build_world();
+        let healer = spawn_creature(&mut world, rltk::Point::new(5, 5), 9, 10);
+
+        attack(
+            &mut world,
+            healer,
+            crate::AttackType::Recover,
+            rltk::Point::new(5, 5),
+        );
+
+        let healths = world.read_storage::<crate::Health>();
+        assert_eq!(healths.get(healer).unwrap().current, 10);
+    }
+
+    // a creature with one extra part to the right of its origin
+    fn spawn_with_part(world: &mut World, point: rltk::Point, hitzone: crate::Hitzone) -> Entity {
+        let part_list = vec![crate::MonsterPart {
+            symbol_map: HashMap::from([(rltk::Point::new(1, 0), rltk::to_cp437('x'))]),
+            health: 4,
+            max_health: 4,
+            kind: crate::PartKind::Head,
+            hitzone,
+            discovered: vec![],
+            on_break: vec![],
+            broken: false,
+        }];
+        let multi = crate::MultiTile {
+            bounds: crate::all_bounds(&part_list),
+            part_list,
+        };
+
+        let ent = world
+            .create_entity()
+            .with(crate::Position {
+                x: point.x,
+                y: point.y,
+            })
+            .with(crate::Health {
+                current: 10,
+                max: 10,
+            })
+            .build();
+
+        let mut map = world.fetch_mut::<crate::Map>();
+        let index = map.point2d_to_index(point);
+        map.track_creature(ent, index, Some(&multi));
+        drop(map);
+
+        world
+            .write_storage::<crate::MultiTile>()
+            .insert(ent, multi)
+            .expect("Failed to insert MultiTile");
+
+        ent
+    }
+
+    #[test]
+    fn weak_points_take_extra_damage() {
+        let mut world = build_world();
+        let player = *world.fetch::<Entity>();
+        let target = spawn_with_part(
+            &mut world,
+            rltk::Point::new(4, 5),
+            crate::Hitzone {
+                blunt: crate::WEAK_POINT_MULT,
+                ..crate::Hitzone::default()
+            },
+        );
+
+        attack(
+            &mut world,
+            player,
+            crate::AttackType::Haymaker,
+            rltk::Point::new(5, 5),
+        );
+
+        let healths = world.read_storage::<crate::Health>();
+        assert_eq!(healths.get(target).unwrap().current, 7);
+
+        let multis = world.read_storage::<crate::MultiTile>();
+        let part = &multis.get(target).unwrap().part_list[0];
+        assert_eq!(part.health, 2);
+        assert_eq!(part.discovered, vec![crate::DamageKind::Blunt]);
+    }
+
+    #[test]
+    fn hard_parts_deflect_attacks() {
+        let mut world = build_world();
+        let player = *world.fetch::<Entity>();
+        let target = spawn_with_part(
+            &mut world,
+            rltk::Point::new(4, 5),
+            crate::Hitzone {
+                cut: 30,
+                ..crate::Hitzone::default()
+            },
+        );
+
+        attack(
+            &mut world,
+            player,
+            crate::AttackType::GreatSwordSlash,
+            rltk::Point::new(5, 5),
+        );
+
+        let healths = world.read_storage::<crate::Health>();
+        assert_eq!(healths.get(target).unwrap().current, 10);
+
+        let multis = world.read_storage::<crate::MultiTile>();
+        assert_eq!(multis.get(target).unwrap().part_list[0].health, 4);
+
+        let log = world.fetch::<crate::gamelog::GameLog>();
+        assert_eq!(log.entries, vec!["Your attack bounced off!".to_string()]);
+    }
+
+    #[test]
+    fn hits_to_the_origin_use_normal_damage() {
+        let mut world = build_world();
+        let player = *world.fetch::<Entity>();
+        let target = spawn_with_part(
+            &mut world,
+            rltk::Point::new(4, 5),
+            crate::Hitzone {
+                cut: 30,
+                ..crate::Hitzone::default()
+            },
+        );
+
+        attack(
+            &mut world,
+            player,
+            crate::AttackType::GreatSwordSlash,
+            rltk::Point::new(4, 5),
+        );
+
+        let healths = world.read_storage::<crate::Health>();
+        assert_eq!(healths.get(target).unwrap().current, 8);
+    }
+
+    fn damage_taken(behavior: crate::Behavior) -> (i32, World, Entity) {
+        let mut world = build_world();
+        let player = *world.fetch::<Entity>();
+        let target = spawn_creature(&mut world, rltk::Point::new(5, 5), 10, 10);
+        world
+            .write_storage::<crate::AiState>()
+            .insert(target, crate::AiState { status: behavior })
+            .expect("Failed to insert AiState");
+
+        attack(
+            &mut world,
+            player,
+            crate::AttackType::Haymaker,
+            rltk::Point::new(5, 5),
+        );
+
+        let damage = 10
+            - world
+                .read_storage::<crate::Health>()
+                .get(target)
+                .unwrap()
+                .current;
+        (damage, world, target)
+    }
+
+    #[test]
+    fn sneak_attacks_deal_extra_damage_and_wake() {
+        let (awake_damage, _, _) = damage_taken(crate::Behavior::Wander);
+        let (sneak_damage, world, target) = damage_taken(crate::Behavior::Sleep);
+
+        assert!(awake_damage > 0);
+        assert_eq!(sneak_damage, awake_damage * SNEAK_ATTACK_MULT);
+
+        let ai_states = world.read_storage::<crate::AiState>();
+        assert!(matches!(
+            ai_states.get(target).unwrap().status,
+            crate::Behavior::Wander
+        ));
+        assert_eq!(
+            world.fetch::<crate::gamelog::GameLog>().entries,
+            vec!["Sneak attack!".to_string()]
+        );
     }
 }
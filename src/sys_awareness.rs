@@ -0,0 +1,216 @@
+use crate::status::{self, StatusKind};
+use crate::Behavior;
+use rltk::{DistanceAlg, Point};
+use specs::prelude::*;
+
+// alert levels needed to wake up, and to go looking for the player
+const ALERT_WAKE: i32 = 10;
+const ALERT_CHASE: i32 = 20;
+const ALERT_MAX: i32 = 30;
+// alert gained per tile a noise reaches past the listener
+const NOISE_ALERT: i32 = 4;
+// sleeping monsters only notice the player right next to them, and only slowly
+const SLEEP_SIGHT_RANGE: f32 = 2.0;
+const SLEEP_SIGHT_ALERT: i32 = 1;
+
+pub struct Noise {
+    pub point: Point,
+    pub radius: i32,
+}
+
+// noises made by the player since the last time monsters listened
+#[derive(Default)]
+pub struct NoiseQueue {
+    pub noises: Vec<Noise>,
+}
+
+pub struct AwarenessSystem;
+
+impl<'a> System<'a> for AwarenessSystem {
+    type SystemData = (
+        Entities<'a>,
+        WriteStorage<'a, crate::Awareness>,
+        WriteStorage<'a, crate::AiState>,
+        ReadStorage<'a, crate::Position>,
+        ReadStorage<'a, crate::Viewshed>,
+        ReadStorage<'a, crate::StatusEffects>,
+        ReadExpect<'a, Entity>,
+        Write<'a, NoiseQueue>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            mut awarenesses,
+            mut ai_states,
+            positions,
+            viewsheds,
+            statuses,
+            player,
+            mut noise_queue,
+        ) = data;
+
+        let player_point = match positions.get(*player) {
+            Some(pos) => pos.as_point(),
+            None => return,
+        };
+
+        for (ent, awareness, state, pos, viewshed) in (
+            &entities,
+            &mut awarenesses,
+            &mut ai_states,
+            &positions,
+            &viewsheds,
+        )
+            .join()
+        {
+            let asleep = matches!(state.status, Behavior::Sleep);
+            let point = pos.as_point();
+            let mut gained = 0;
+            let mut heard_at = None;
+
+            if viewshed.visible.contains(&player_point) {
+                if !asleep {
+                    gained = ALERT_MAX;
+                } else if DistanceAlg::Chebyshev.distance2d(point, player_point)
+                    <= SLEEP_SIGHT_RANGE
+                {
+                    gained = SLEEP_SIGHT_ALERT;
+                }
+            }
+
+            for noise in noise_queue.noises.iter() {
+                let dist = DistanceAlg::Chebyshev.distance2d(point, noise.point) as i32;
+                if dist <= noise.radius {
+                    gained += NOISE_ALERT * (noise.radius - dist + 1);
+                    heard_at = Some(noise.point);
+                }
+            }
+
+            if gained > 0 {
+                awareness.alert = std::cmp::min(ALERT_MAX, awareness.alert + gained);
+            } else {
+                awareness.alert = std::cmp::max(0, awareness.alert - 1);
+            }
+
+            match state.status {
+                // monsters put to sleep stay down until it wears off
+                Behavior::Sleep
+                    if awareness.alert >= ALERT_WAKE
+                        && !status::has_status(&statuses, ent, StatusKind::Sleep) =>
+                {
+                    state.status = Behavior::Wander;
+                }
+                // go check out whatever made the noise
+                Behavior::Wander if awareness.alert >= ALERT_CHASE => {
+                    if let Some(target_point) = heard_at {
+                        state.status = Behavior::Chase { target_point };
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        noise_queue.noises.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_world() -> World {
+        let mut world = crate::test_util::build_world(10, 10, Point::new(0, 0));
+        System::setup(&mut AwarenessSystem, &mut world);
+        world
+    }
+
+    fn spawn_sleeper(world: &mut World, point: Point, visible: Vec<Point>) -> Entity {
+        world
+            .create_entity()
+            .with(crate::Position {
+                x: point.x,
+                y: point.y,
+            })
+            .with(crate::Viewshed {
+                visible,
+                dirty: false,
+                range: 8,
+            })
+            .with(crate::AiState {
+                status: Behavior::Sleep,
+            })
+            .with(crate::Awareness::default())
+            .build()
+    }
+
+    fn make_noise(world: &mut World, point: Point, radius: i32) {
+        world
+            .fetch_mut::<NoiseQueue>()
+            .noises
+            .push(Noise { point, radius });
+    }
+
+    fn run(world: &mut World) {
+        AwarenessSystem.run_now(world);
+        world.maintain();
+    }
+
+    fn is_asleep(world: &World, ent: Entity) -> bool {
+        let ai_states = world.read_storage::<crate::AiState>();
+        matches!(ai_states.get(ent).unwrap().status, Behavior::Sleep)
+    }
+
+    #[test]
+    fn loud_noise_wakes_nearby_monsters() {
+        let mut world = build_world();
+        let near = spawn_sleeper(&mut world, Point::new(5, 5), vec![]);
+        let far = spawn_sleeper(&mut world, Point::new(20, 20), vec![]);
+
+        make_noise(&mut world, Point::new(4, 5), 8);
+        run(&mut world);
+
+        assert!(!is_asleep(&world, near));
+        assert!(is_asleep(&world, far));
+        assert!(world.fetch::<NoiseQueue>().noises.is_empty());
+    }
+
+    #[test]
+    fn sleeping_monsters_notice_the_player_slowly() {
+        let mut world = build_world();
+        let ent = spawn_sleeper(&mut world, Point::new(1, 1), vec![Point::new(0, 0)]);
+
+        for _ in 0..ALERT_WAKE - 1 {
+            run(&mut world);
+        }
+        assert!(is_asleep(&world, ent));
+
+        run(&mut world);
+        assert!(!is_asleep(&world, ent));
+    }
+
+    #[test]
+    fn alert_monsters_investigate_noises() {
+        let mut world = build_world();
+        let ent = spawn_sleeper(&mut world, Point::new(5, 5), vec![]);
+        world
+            .write_storage::<crate::Awareness>()
+            .get_mut(ent)
+            .unwrap()
+            .alert = ALERT_CHASE;
+        world
+            .write_storage::<crate::AiState>()
+            .get_mut(ent)
+            .unwrap()
+            .status = Behavior::Wander;
+
+        make_noise(&mut world, Point::new(7, 5), 3);
+        run(&mut world);
+
+        let ai_states = world.read_storage::<crate::AiState>();
+        match ai_states.get(ent).unwrap().status {
+            Behavior::Chase { target_point } => assert_eq!(target_point, Point::new(7, 5)),
+            _ => panic!("monster should be chasing"),
+        }
+    }
+}
@@ -13,6 +13,9 @@ impl<'a> System<'a> for DeathSystem {
         ReadStorage<'a, crate::MultiTile>,
         ReadStorage<'a, crate::MissionTarget>,
         WriteExpect<'a, crate::MissionInfo>,
+        ReadStorage<'a, crate::Viewable>,
+        WriteExpect<'a, rltk::RandomNumberGenerator>,
+        WriteExpect<'a, crate::gamelog::GameLog>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
@@ -26,6 +29,9 @@ impl<'a> System<'a> for DeathSystem {
             multitiles,
             targets,
             mut m_info,
+            viewables,
+            mut rng,
+            mut log,
         ) = data;
         let mut dead = Vec::new();
 
@@ -43,6 +49,29 @@ impl<'a> System<'a> for DeathSystem {
                         m_info.remove(ent);
                     }
 
+                    if let Some(view) = viewables.get(ent) {
+                        if let Some(drop_info) = crate::data::get_drop_info(&view.name) {
+                            // broken parts yield extra rare materials
+                            let broken_parts = multis.map_or(0, |multi| {
+                                multi
+                                    .part_list
+                                    .iter()
+                                    .filter(|part| part.health <= 0)
+                                    .count()
+                            });
+
+                            let drops = drop_info.roll_drops(broken_parts, &mut rng);
+                            for material in drops.iter() {
+                                m_info.add_material(material);
+                            }
+
+                            if !drops.is_empty() {
+                                let names: Vec<&str> = drops.iter().map(|s| s.as_str()).collect();
+                                log.add(&format!("Carved {}", names.join(", ")));
+                            }
+                        }
+                    }
+
                     if m_info.is_done() {
                         *run_state = crate::RunState::Dead { success: true };
                     }
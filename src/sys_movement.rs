@@ -7,12 +7,13 @@ impl<'a> System<'a> for MovementSystem {
         Entities<'a>,
         WriteExpect<'a, crate::Map>,
         ReadStorage<'a, crate::Moveset>,
-        ReadStorage<'a, crate::MultiTile>,
+        WriteStorage<'a, crate::MultiTile>,
         WriteStorage<'a, crate::Position>,
         WriteStorage<'a, crate::MoveIntent>,
         WriteStorage<'a, crate::AttackIntent>,
         WriteStorage<'a, crate::Viewshed>,
         WriteStorage<'a, crate::Facing>,
+        WriteStorage<'a, crate::StuckInfo>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
@@ -20,22 +21,24 @@ impl<'a> System<'a> for MovementSystem {
             entities,
             mut map,
             movesets,
-            multis,
+            mut multis,
             mut positions,
             mut movements,
             mut attacks,
             mut viewsheds,
             mut facings,
+            mut stuck_infos,
         ) = data;
 
-        for (ent, pos, movement, moveset, multi, viewshed, facing) in (
+        for (ent, pos, movement, moveset, mut multi, viewshed, facing, stuck) in (
             &entities,
             &mut positions,
             &movements,
             (&movesets).maybe(),
-            (&multis).maybe(),
+            (&mut multis).maybe(),
             (&mut viewsheds).maybe(),
             (&mut facings).maybe(),
+            (&mut stuck_infos).maybe(),
         )
             .join()
         {
@@ -43,17 +46,25 @@ impl<'a> System<'a> for MovementSystem {
             let mut attack_pos = None;
 
             if let Some(facing) = facing {
-                if let Some(dir) = movement.force_facing {
-                    facing.direction = dir;
-                } else if let Some(dir) =
-                    crate::Direction::get_direction_towards(pos.as_point(), new_pos)
-                {
-                    facing.direction = dir;
+                let next_facing = movement
+                    .force_facing
+                    .or_else(|| crate::Direction::get_direction_towards(pos.as_point(), new_pos));
+
+                if let Some(dir) = next_facing {
+                    match &mut multi {
+                        None => facing.direction = dir,
+                        Some(multi) => {
+                            // multi-tile bodies can only turn if their new shape fits where they are
+                            if turn_body(ent, &mut map, pos, multi, facing.direction, dir) {
+                                facing.direction = dir;
+                            }
+                        }
+                    }
                 }
             }
 
             // check for the player at the destination, since we should already be pathing around other entities
-            if let Some(multi) = multi {
+            if let Some(multi) = &multi {
                 attack_pos = check_for_entity_at(ent, &mut *map, multi, new_pos);
             }
 
@@ -76,13 +87,22 @@ impl<'a> System<'a> for MovementSystem {
                 }
                 None => {
                     // update the position if we successfully moved to new_pos
-                    if map.move_creature(ent, pos.as_point(), new_pos, multi) {
+                    if map.move_creature(ent, pos.as_point(), new_pos, multi.as_deref()) {
                         pos.x = new_pos.x;
                         pos.y = new_pos.y;
 
                         if let Some(viewshed) = viewshed {
                             viewshed.dirty = true;
                         }
+
+                        if let Some(stuck) = stuck {
+                            stuck.failed_moves = 0;
+                            stuck.clear_moves += 1;
+                        }
+                    } else if let Some(stuck) = stuck {
+                        stuck.failed_moves += 1;
+                        stuck.clear_moves = 0;
+                        stuck.blocked_dir = Some(new_pos - pos.as_point());
                     }
                 }
             }
@@ -92,6 +112,39 @@ impl<'a> System<'a> for MovementSystem {
     }
 }
 
+// rotate a multi-tile body in place to face a new direction
+// returns false if the rotated body would overlap a wall or another creature
+fn turn_body(
+    ent: Entity,
+    map: &mut crate::Map,
+    pos: &crate::Position,
+    multi: &mut crate::MultiTile,
+    from: crate::Direction,
+    to: crate::Direction,
+) -> bool {
+    let turns = from.turns_to(to);
+    if turns == 0 {
+        return true;
+    }
+
+    let part_list = crate::rotate_parts(&multi.part_list, turns);
+    let rotated = crate::MultiTile {
+        bounds: crate::all_bounds(&part_list),
+        part_list,
+    };
+
+    if !map.is_exit_valid_for(pos.x, pos.y, ent, Some(&rotated)) {
+        return false;
+    }
+
+    let index = map.get_index(pos.x, pos.y);
+    map.untrack_creature(index, Some(multi));
+    map.track_creature(ent, index, Some(&rotated));
+    *multi = rotated;
+
+    true
+}
+
 fn check_for_entity_at(
     ent: Entity,
     map: &mut crate::Map,
@@ -114,3 +167,165 @@ fn check_for_entity_at(
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rltk::Point;
+    use std::collections::HashMap;
+
+    fn build_world() -> World {
+        let mut world = crate::test_util::build_world(12, 12, Point::new(0, 0));
+        System::setup(&mut MovementSystem, &mut world);
+        world
+    }
+
+    // a monster facing north with one part to its right
+    fn spawn(world: &mut World, point: Point) -> Entity {
+        let part_list = vec![crate::MonsterPart {
+            symbol_map: HashMap::from([(Point::new(1, 0), rltk::to_cp437('─'))]),
+            health: 1,
+            max_health: 1,
+            kind: crate::PartKind::Body,
+            hitzone: crate::Hitzone::default(),
+            discovered: vec![],
+            on_break: vec![],
+            broken: false,
+        }];
+        let multi = crate::MultiTile {
+            bounds: crate::all_bounds(&part_list),
+            part_list,
+        };
+
+        let ent = world
+            .create_entity()
+            .with(crate::Position {
+                x: point.x,
+                y: point.y,
+            })
+            .with(crate::Facing {
+                direction: crate::Direction::N,
+            })
+            .build();
+
+        let mut map = world.fetch_mut::<crate::Map>();
+        let index = map.get_index(point.x, point.y);
+        map.track_creature(ent, index, Some(&multi));
+        drop(map);
+
+        world
+            .write_storage::<crate::MultiTile>()
+            .insert(ent, multi)
+            .expect("Failed to insert MultiTile");
+
+        ent
+    }
+
+    fn step(world: &mut World, ent: Entity, loc: Point) {
+        world
+            .write_storage::<crate::MoveIntent>()
+            .insert(
+                ent,
+                crate::MoveIntent {
+                    loc,
+                    force_facing: None,
+                },
+            )
+            .expect("Failed to insert movement");
+
+        MovementSystem.run_now(world);
+        world.maintain();
+    }
+
+    fn parts(world: &World, ent: Entity) -> Vec<(Point, rltk::FontCharType)> {
+        let multis = world.read_storage::<crate::MultiTile>();
+        multis.get(ent).unwrap().part_list[0]
+            .symbol_map
+            .iter()
+            .map(|(pos, glyph)| (*pos, *glyph))
+            .collect()
+    }
+
+    fn facing(world: &World, ent: Entity) -> crate::Direction {
+        world
+            .read_storage::<crate::Facing>()
+            .get(ent)
+            .unwrap()
+            .direction
+    }
+
+    #[test]
+    fn turning_rotates_parts() {
+        let mut world = build_world();
+        let ent = spawn(&mut world, Point::new(5, 5));
+
+        step(&mut world, ent, Point::new(6, 5));
+
+        assert_eq!(facing(&world, ent), crate::Direction::E);
+        assert_eq!(
+            parts(&world, ent),
+            vec![(Point::new(0, 1), rltk::to_cp437('│'))]
+        );
+
+        let map = world.fetch::<crate::Map>();
+        assert_eq!(map.creature_map.get(&map.get_index(6, 6)), Some(&ent));
+        assert!(!map.creature_map.contains_key(&map.get_index(7, 5)));
+    }
+
+    #[test]
+    fn turning_around_flips_parts() {
+        let mut world = build_world();
+        let ent = spawn(&mut world, Point::new(5, 5));
+
+        step(&mut world, ent, Point::new(5, 6));
+
+        assert_eq!(facing(&world, ent), crate::Direction::S);
+        assert_eq!(
+            parts(&world, ent),
+            vec![(Point::new(-1, 0), rltk::to_cp437('─'))]
+        );
+    }
+
+    #[test]
+    fn blocked_turn_keeps_old_facing() {
+        let mut world = build_world();
+        let ent = spawn(&mut world, Point::new(5, 1));
+
+        // facing west would put the part in the wall above
+        step(&mut world, ent, Point::new(4, 1));
+
+        assert_eq!(facing(&world, ent), crate::Direction::N);
+        assert_eq!(
+            parts(&world, ent),
+            vec![(Point::new(1, 0), rltk::to_cp437('─'))]
+        );
+
+        let positions = world.read_storage::<crate::Position>();
+        assert_eq!(positions.get(ent).unwrap().as_point(), Point::new(4, 1));
+    }
+
+    #[test]
+    fn failed_moves_are_counted() {
+        let mut world = build_world();
+        let ent = spawn(&mut world, Point::new(5, 5));
+        world
+            .write_storage::<crate::StuckInfo>()
+            .insert(ent, crate::StuckInfo::default())
+            .expect("Failed to insert StuckInfo");
+
+        // straight into the wall
+        step(&mut world, ent, Point::new(5, 0));
+        {
+            let stuck_infos = world.read_storage::<crate::StuckInfo>();
+            let stuck = stuck_infos.get(ent).unwrap();
+            assert_eq!(stuck.failed_moves, 1);
+            assert_eq!(stuck.blocked_dir, Some(Point::new(0, -5)));
+        }
+
+        step(&mut world, ent, Point::new(5, 4));
+        let stuck_infos = world.read_storage::<crate::StuckInfo>();
+        let stuck = stuck_infos.get(ent).unwrap();
+        assert_eq!(stuck.failed_moves, 0);
+        assert_eq!(stuck.clear_moves, 1);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::{BreakEffect, PartKind};
 use specs::prelude::*;
 
 pub struct PartBreakSystem;
@@ -7,19 +8,249 @@ impl<'a> System<'a> for PartBreakSystem {
         Entities<'a>,
         ReadStorage<'a, crate::Position>,
         WriteStorage<'a, crate::MultiTile>,
+        WriteStorage<'a, crate::Moveset>,
+        WriteStorage<'a, crate::Schedulable>,
+        WriteStorage<'a, crate::AiState>,
+        ReadStorage<'a, crate::Viewable>,
+        ReadStorage<'a, crate::Renderable>,
+        WriteExpect<'a, crate::Map>,
+        WriteExpect<'a, crate::gamelog::GameLog>,
+        Read<'a, LazyUpdate>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (entities, positions, mut multitiles) = data;
+        let (
+            entities,
+            positions,
+            mut multitiles,
+            mut movesets,
+            mut schedulables,
+            mut ai_states,
+            viewables,
+            renderables,
+            mut map,
+            mut log,
+            lazy,
+        ) = data;
+
+        for (ent, pos, multis) in (&entities, &positions, &mut multitiles).join() {
+            let name = viewables
+                .get(ent)
+                .map_or("monster".to_string(), |view| view.name.clone());
+            let mut severed = false;
 
-        for (_, _, multis) in (&entities, &positions, &mut multitiles).join() {
             for part in multis.part_list.iter_mut() {
-                if part.health <= 0 {
+                if part.health > 0 || part.broken {
+                    continue;
+                }
+
+                part.broken = true;
+
+                for effect in part.on_break.iter() {
+                    match effect {
+                        BreakEffect::LoseAttack(attack) => {
+                            if let Some(moveset) = movesets.get_mut(ent) {
+                                moveset.moves.retain(|(potential, _)| potential != attack);
+                            }
+                        }
+                        BreakEffect::WeakenAttack(attack) => {
+                            if let Some(moveset) = movesets.get_mut(ent) {
+                                for (potential, chance) in moveset.moves.iter_mut() {
+                                    if potential == attack {
+                                        *chance /= 2.0;
+                                    }
+                                }
+                            }
+                        }
+                        BreakEffect::Slow => {
+                            if let Some(sched) = schedulables.get_mut(ent) {
+                                sched.delta = std::cmp::max(1, sched.delta - 1);
+                            }
+                        }
+                        BreakEffect::Flee => {
+                            if let Some(state) = ai_states.get_mut(ent) {
+                                state.status = crate::Behavior::Flee;
+                            }
+                        }
+                    }
+                }
+
+                if part.kind == PartKind::Tail {
+                    // the tail drops where it was as a separate object
+                    if let Some((part_pos, symbol)) = part.symbol_map.iter().next() {
+                        let point = pos.as_point() + *part_pos;
+                        let fg = renderables
+                            .get(ent)
+                            .map_or(crate::text_color(), |render| render.fg);
+
+                        let item = lazy
+                            .create_entity(&entities)
+                            .with(crate::Position {
+                                x: point.x,
+                                y: point.y,
+                            })
+                            .with(crate::Renderable {
+                                symbol: *symbol,
+                                fg,
+                                bg: crate::bg_color(),
+                            })
+                            .with(crate::Viewable {
+                                name: format!("{} tail", name),
+                                description: vec!["Step on it to carve".to_string()],
+                                seen: false,
+                            })
+                            .with(crate::Item)
+                            .with(crate::Carveable {
+                                monster: name.clone(),
+                            })
+                            .build();
+                        map.track_item(item, point);
+                    }
+
+                    severed = true;
+                    log.add(&format!("The {}'s tail was severed!", name));
+                } else {
                     for v in part.symbol_map.values_mut() {
                         *v = rltk::to_cp437('x');
                     }
+
+                    log.add(&format!("The {}'s {} broke!", name, part.kind.name()));
                 }
             }
+
+            if severed {
+                multis
+                    .part_list
+                    .retain(|part| !part.broken || part.kind != PartKind::Tail);
+                multis.bounds = crate::all_bounds(&multis.part_list);
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AttackType;
+    use std::collections::HashMap;
+
+    fn build_world() -> World {
+        let mut world = crate::test_util::build_world(10, 10, rltk::Point::new(0, 0));
+        System::setup(&mut PartBreakSystem, &mut world);
+        world.register::<crate::Item>();
+        world.register::<crate::Carveable>();
+        world
+    }
+
+    // a monster with a single broken part to the right of its origin
+    fn spawn_broken(world: &mut World, kind: PartKind, on_break: Vec<BreakEffect>) -> Entity {
+        let part_list = vec![crate::MonsterPart {
+            symbol_map: HashMap::from([(rltk::Point::new(1, 0), rltk::to_cp437('~'))]),
+            health: 0,
+            max_health: 2,
+            kind,
+            hitzone: crate::Hitzone::default(),
+            discovered: vec![],
+            on_break,
+            broken: false,
+        }];
+
+        world
+            .create_entity()
+            .with(crate::Position { x: 4, y: 4 })
+            .with(crate::Viewable {
+                name: "Crab".to_string(),
+                description: vec![],
+                seen: false,
+            })
+            .with(crate::Moveset {
+                moves: vec![(AttackType::Sweep, 0.5), (AttackType::Punch, 0.5)],
+                bump_attack: AttackType::Punch,
+            })
+            .with(crate::Schedulable {
+                current: 0,
+                base: 24,
+                delta: 4,
+            })
+            .with(crate::MultiTile {
+                bounds: crate::all_bounds(&part_list),
+                part_list,
+            })
+            .build()
+    }
+
+    fn run(world: &mut World) {
+        PartBreakSystem.run_now(world);
+        world.maintain();
+    }
+
+    #[test]
+    fn broken_part_removes_attack() {
+        let mut world = build_world();
+        let ent = spawn_broken(
+            &mut world,
+            PartKind::Body,
+            vec![BreakEffect::LoseAttack(AttackType::Sweep)],
+        );
+
+        run(&mut world);
+
+        let movesets = world.read_storage::<crate::Moveset>();
+        assert_eq!(
+            movesets.get(ent).unwrap().moves,
+            vec![(AttackType::Punch, 0.5)]
+        );
+
+        let log = world.fetch::<crate::gamelog::GameLog>();
+        assert_eq!(log.entries, vec!["The Crab's body broke!".to_string()]);
+    }
+
+    #[test]
+    fn break_effects_only_apply_once() {
+        let mut world = build_world();
+        let ent = spawn_broken(
+            &mut world,
+            PartKind::Head,
+            vec![
+                BreakEffect::WeakenAttack(AttackType::Sweep),
+                BreakEffect::Slow,
+            ],
+        );
+
+        run(&mut world);
+        run(&mut world);
+
+        let movesets = world.read_storage::<crate::Moveset>();
+        assert_eq!(
+            movesets.get(ent).unwrap().moves[0],
+            (AttackType::Sweep, 0.25)
+        );
+
+        let scheds = world.read_storage::<crate::Schedulable>();
+        assert_eq!(scheds.get(ent).unwrap().delta, 3);
+
+        assert_eq!(world.fetch::<crate::gamelog::GameLog>().entries.len(), 1);
+    }
+
+    #[test]
+    fn broken_tail_is_severed() {
+        let mut world = build_world();
+        let ent = spawn_broken(&mut world, PartKind::Tail, vec![]);
+
+        run(&mut world);
+
+        let multis = world.read_storage::<crate::MultiTile>();
+        assert!(multis.get(ent).unwrap().part_list.is_empty());
+
+        let map = world.fetch::<crate::Map>();
+        let item = *map.item_map.get(&map.get_index(5, 4)).unwrap();
+        let carveables = world.read_storage::<crate::Carveable>();
+        assert_eq!(carveables.get(item).unwrap().monster, "Crab");
+
+        let positions = world.read_storage::<crate::Position>();
+        assert_eq!(
+            positions.get(item).unwrap().as_point(),
+            rltk::Point::new(5, 4)
+        );
+    }
+}
@@ -10,18 +10,27 @@ impl<'a> System<'a> for PartMoveSystem {
         WriteStorage<'a, crate::PartMoveIntent>,
         WriteStorage<'a, crate::MultiTile>,
         WriteStorage<'a, crate::PushForce>,
+        ReadStorage<'a, crate::Facing>,
         ReadExpect<'a, crate::Map>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (entities, positions, mut part_moves, mut multitiles, mut pushes, map) = data;
-
-        for (ent, ent_pos, moves, multis) in
-            (&entities, &positions, &part_moves, &mut multitiles).join()
+        let (entities, positions, mut part_moves, mut multitiles, mut pushes, facings, map) = data;
+
+        for (ent, ent_pos, moves, multis, facing) in (
+            &entities,
+            &positions,
+            &part_moves,
+            &mut multitiles,
+            (&facings).maybe(),
+        )
+            .join()
         {
             let mut pushed_ents = Vec::new();
 
             for (i, dir) in moves.part_delta.iter().enumerate() {
+                // part movements are written for a monster facing north
+                let dir = &facing.map_or(*dir, |facing| facing.direction.rotate_point(*dir));
                 let mut new_symbol_map = HashMap::new();
 
                 for (part_pos, symbol) in &multis.part_list[i].symbol_map {
@@ -1,4 +1,4 @@
-use super::{Heal, Health, Map, Position, RunState, Schedulable};
+use super::{Carveable, Heal, Health, Map, Position, RunState, Schedulable};
 use specs::prelude::*;
 
 pub struct PickupSystem;
@@ -13,11 +13,27 @@ impl<'a> System<'a> for PickupSystem {
         ReadStorage<'a, Schedulable>,
         ReadStorage<'a, Heal>,
         WriteStorage<'a, Health>,
+        ReadStorage<'a, Carveable>,
+        WriteExpect<'a, crate::MissionInfo>,
+        WriteExpect<'a, rltk::RandomNumberGenerator>,
+        WriteExpect<'a, crate::gamelog::GameLog>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (entities, player, mut map, mut run_state, positions, scheds, heals, mut healths) =
-            data;
+        let (
+            entities,
+            player,
+            mut map,
+            mut run_state,
+            positions,
+            scheds,
+            heals,
+            mut healths,
+            carveables,
+            mut m_info,
+            mut rng,
+            mut log,
+        ) = data;
         let mut consumed = Vec::new();
 
         for (ent, pos, mut health, _) in (&entities, &positions, &mut healths, &scheds).join() {
@@ -27,8 +43,22 @@ impl<'a> System<'a> for PickupSystem {
                 None => {}
                 Some(item_ent) => {
                     if let Some(healing) = heals.get(item_ent) {
-                        health.current += healing.amount as i32;
-                        health.current = std::cmp::min(health.current, health.max);
+                        health.heal(healing.amount as i32);
+                        consumed.push(item_ent);
+                    } else if let Some(carve) = carveables.get(item_ent) {
+                        // only the player can carve, anyone else leaves it where it is
+                        if ent != *player {
+                            map.track_item(item_ent, point);
+                            continue;
+                        }
+
+                        if let Some(drop_info) = crate::data::get_drop_info(&carve.monster) {
+                            if let Some(material) = drop_info.roll_severed(&mut rng) {
+                                m_info.add_material(material);
+                                log.add(&format!("Carved {}", material));
+                            }
+                        }
+
                         consumed.push(item_ent);
                     }
                 }
@@ -0,0 +1,69 @@
+use rltk::Algorithm2D;
+use specs::prelude::*;
+
+pub struct ProjectileSystem;
+
+impl<'a> System<'a> for ProjectileSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, crate::Map>,
+        WriteStorage<'a, crate::CanActFlag>,
+        WriteStorage<'a, crate::Position>,
+        WriteStorage<'a, crate::Projectile>,
+        WriteStorage<'a, crate::AttackIntent>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (entities, map, mut can_act, mut positions, mut projectiles, mut attacks) = data;
+        let mut turn_done = Vec::new();
+
+        for (ent, _turn, pos, projectile) in
+            (&entities, &can_act, &mut positions, &mut projectiles).join()
+        {
+            turn_done.push(ent);
+
+            // move one tile at a time so we don't skip over anything
+            for _ in 0..projectile.speed {
+                let next_point =
+                    crate::Direction::point_in_direction(pos.as_point(), projectile.dir);
+
+                if !map.in_bounds(next_point)
+                    || map.tiles[map.point2d_to_index(next_point)] == crate::TileType::Wall
+                {
+                    entities.delete(ent).expect("Failed to remove projectile");
+                    break;
+                }
+
+                pos.x = next_point.x;
+                pos.y = next_point.y;
+
+                // hit the first creature in the way, the attack system cleans up the projectile
+                if let Some(target) = map.creature_map.get(&map.point2d_to_index(next_point)) {
+                    if *target != projectile.source {
+                        attacks
+                            .insert(
+                                ent,
+                                crate::attack_type::get_attack_intent(
+                                    projectile.hit,
+                                    next_point,
+                                    None,
+                                ),
+                            )
+                            .expect("Failed to insert attack from projectile");
+                        break;
+                    }
+                }
+
+                projectile.range -= 1;
+                if projectile.range <= 0 {
+                    entities.delete(ent).expect("Failed to remove projectile");
+                    break;
+                }
+            }
+        }
+
+        for done in turn_done.iter() {
+            can_act.remove(*done);
+        }
+    }
+}
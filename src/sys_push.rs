@@ -1,9 +1,20 @@
-use rltk::Algorithm2D;
+use rltk::{Algorithm2D, Point};
 use specs::prelude::*;
 
 pub struct PushSystem;
 
-const MAX_ITERS: i32 = 100;
+enum Blocker {
+    Wall,
+    Creature(Entity),
+}
+
+struct PushResult {
+    dest: Point,
+    // damage taken from slamming into things
+    impact: i32,
+    // whatever force is left over gets passed on to the creature we ran into
+    transfer: Option<(Entity, Point)>,
+}
 
 impl<'a> System<'a> for PushSystem {
     type SystemData = (
@@ -12,137 +23,347 @@ impl<'a> System<'a> for PushSystem {
         WriteStorage<'a, crate::Position>,
         ReadStorage<'a, crate::MultiTile>,
         WriteStorage<'a, crate::Viewshed>,
+        WriteStorage<'a, crate::Health>,
+        WriteStorage<'a, crate::HitFlag>,
         WriteExpect<'a, crate::Map>,
+        WriteExpect<'a, crate::ParticleBuilder>,
+        ReadExpect<'a, Entity>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (entities, mut pushes, mut positions, multitiles, mut viewsheds, mut map) = data;
-
-        for (ent, force, pos, multis, view) in (
-            &entities,
-            &pushes,
-            &mut positions,
-            (&multitiles).maybe(),
-            (&mut viewsheds).maybe(),
-        )
+        let (
+            entities,
+            mut pushes,
+            mut positions,
+            multitiles,
+            mut viewsheds,
+            mut healths,
+            mut hit_flags,
+            mut map,
+            mut p_builder,
+            player,
+        ) = data;
+
+        let mut queue: Vec<(Entity, Point)> = (&entities, &pushes)
             .join()
-        {
-            let final_dest = match multis {
-                None => apply_forces(ent, force, pos, &mut *map),
-                Some(multi) => apply_forces_to_multi(ent, force, pos, multi, &mut *map),
+            .map(|(ent, force)| (ent, force.delta))
+            .collect();
+        pushes.clear();
+
+        // secondary pushes are added to the end of the queue as collisions happen
+        let mut index = 0;
+        while index < queue.len() {
+            let (ent, force) = queue[index];
+            index += 1;
+
+            let pos = match positions.get_mut(ent) {
+                Some(pos) => pos,
+                None => continue,
             };
 
+            let result = apply_forces(ent, force, pos.as_point(), multitiles.get(ent), &mut map);
+
             *pos = crate::Position {
-                x: final_dest.x,
-                y: final_dest.y,
+                x: result.dest.x,
+                y: result.dest.y,
             };
 
-            if let Some(view) = view {
+            if let Some(view) = viewsheds.get_mut(ent) {
                 view.dirty = true;
             }
-        }
 
-        pushes.clear();
+            if result.impact > 0 {
+                if let Some(health) = healths.get_mut(ent) {
+                    health.current -= result.impact;
+                    p_builder.make_hit_particle(result.dest);
+
+                    // let the player's weapon know it got interrupted
+                    if ent == *player {
+                        hit_flags
+                            .insert(ent, crate::HitFlag)
+                            .expect("Failed to insert HitFlag");
+                    }
+                }
+            }
+
+            if let Some(transfer) = result.transfer {
+                queue.push(transfer);
+            }
+        }
     }
 }
 
+// move one tile at a time until the force is used up
+// running into a creature stops us and passes the rest of the force on to them
+// running into a wall hurts and bounces off along whichever axis is open, and if nothing is open we stop where we are
 fn apply_forces(
     ent: Entity,
-    force: &crate::PushForce,
-    pos: &crate::Position,
+    force: Point,
+    start: Point,
+    multi: Option<&crate::MultiTile>,
     map: &mut crate::Map,
-) -> rltk::Point {
-    let mut prev_point;
-    let mut new_point = pos.as_point();
-    let mut applied_force = force.delta;
-
-    for _ in 0..MAX_ITERS {
-        prev_point = new_point;
-        new_point = prev_point + applied_force;
-
-        match calc_dest_force(ent, applied_force, prev_point, new_point, map) {
-            None => return new_point,
-            Some((updated_point, new_force)) => {
-                applied_force = new_force;
-                new_point = updated_point;
+) -> PushResult {
+    let mut result = PushResult {
+        dest: start,
+        impact: 0,
+        transfer: None,
+    };
+    let mut remaining = force;
+
+    while remaining != Point::zero() {
+        let step = Point::new(remaining.x.signum(), remaining.y.signum());
+
+        let (step, new_remaining) = match find_blocker(ent, result.dest + step, multi, map) {
+            None => (step, remaining),
+            Some(Blocker::Creature(other)) => {
+                result.impact += force_magnitude(remaining);
+
+                // the step we couldn't take is spent on the collision
+                let leftover = remaining - step;
+                if leftover != Point::zero() {
+                    result.transfer = Some((other, leftover));
+                }
+
+                break;
+            }
+            Some(Blocker::Wall) => {
+                result.impact += force_magnitude(remaining);
+
+                let bounces = [
+                    (
+                        Point::new(-step.x, step.y),
+                        Point::new(-remaining.x, remaining.y),
+                    ),
+                    (
+                        Point::new(step.x, -step.y),
+                        Point::new(remaining.x, -remaining.y),
+                    ),
+                ];
+
+                match bounces
+                    .iter()
+                    .find(|(step, _)| find_blocker(ent, result.dest + *step, multi, map).is_none())
+                {
+                    None => break,
+                    Some(bounce) => *bounce,
+                }
             }
+        };
+
+        let next_point = result.dest + step;
+        if !map.move_creature(ent, result.dest, next_point, multi) {
+            break;
         }
+
+        result.dest = next_point;
+        remaining = new_remaining - step;
     }
 
-    dbg!("can't find a place to move to");
-    rltk::Point::zero()
+    result
+}
+
+fn force_magnitude(force: Point) -> i32 {
+    std::cmp::max(force.x.abs(), force.y.abs())
 }
 
-fn calc_dest_force(
-    _ent: Entity,
-    applied_force: rltk::Point,
-    prev_point: rltk::Point,
-    new_point: rltk::Point,
+// check every tile we would cover for other creatures, then for walls
+fn find_blocker(
+    ent: Entity,
+    point: Point,
+    multi: Option<&crate::MultiTile>,
     map: &mut crate::Map,
-) -> Option<(rltk::Point, rltk::Point)> {
-    let new_idx = map.get_index(new_point.x, new_point.y);
+) -> Option<Blocker> {
+    let mut covered = vec![point];
+    if let Some(multi) = multi {
+        for part in &multi.part_list {
+            covered.extend(part.symbol_map.keys().map(|part_pos| point + *part_pos));
+        }
+    }
+
+    // the player doesn't block tiles, so the creature map is checked directly
+    for tile in covered {
+        if !map.in_bounds(tile) {
+            return Some(Blocker::Wall);
+        }
+
+        if let Some(other) = map.creature_map.get(&map.point2d_to_index(tile)) {
+            if *other != ent {
+                return Some(Blocker::Creature(*other));
+            }
+        }
+    }
 
-    // if we can fit in the new tile, we are done
-    if !map.blocked_tiles[new_idx] {
-        return None;
+    if !map.is_exit_valid_for(point.x, point.y, ent, multi) {
+        return Some(Blocker::Wall);
     }
 
-    // TODO: assuming forces at most 1 tile (will also need to update collision checking sys_partmove to support higher forces)
-    let refl_h_force = rltk::Point::new(-applied_force.x, applied_force.y);
-    let refl_h_pos = new_point + refl_h_force;
-    let refl_h_idx = map.get_index(refl_h_pos.x, refl_h_pos.y);
-    if !map.blocked_tiles[refl_h_idx] {
-        return Some((new_point, refl_h_force));
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn build_world() -> World {
+        let mut world = crate::test_util::build_world(12, 12, Point::new(0, 0));
+        System::setup(&mut PushSystem, &mut world);
+        world
     }
 
-    let refl_v_force = rltk::Point::new(applied_force.x, -applied_force.y);
-    let refl_v_pos = new_point + refl_v_force;
-    let refl_v_idx = map.get_index(refl_v_pos.x, refl_v_pos.y);
-    if !map.blocked_tiles[refl_v_idx] {
-        return Some((new_point, refl_v_force));
+    fn spawn(world: &mut World, point: Point, multi: Option<crate::MultiTile>) -> Entity {
+        let mut builder = world
+            .create_entity()
+            .with(crate::Position {
+                x: point.x,
+                y: point.y,
+            })
+            .with(crate::Health {
+                current: 10,
+                max: 10,
+            });
+
+        if let Some(multi) = &multi {
+            builder = builder.with(crate::MultiTile {
+                part_list: multi.part_list.clone(),
+                bounds: multi.bounds,
+            });
+        }
+
+        let ent = builder.build();
+        let mut map = world.fetch_mut::<crate::Map>();
+        let index = map.point2d_to_index(point);
+        map.track_creature(ent, index, multi.as_ref());
+
+        ent
     }
 
-    // Move to an open adjacent square
-    let mut open_list = Vec::new();
-    for dx in -1..=1 {
-        for dy in -1..1 {
-            if dx == 0 && dy == 0 {
-                continue;
-            }
+    fn push(world: &mut World, ent: Entity, delta: Point) -> Point {
+        world
+            .write_storage::<crate::PushForce>()
+            .insert(ent, crate::PushForce { delta })
+            .expect("Failed to insert push");
 
-            let new_x = prev_point.x + dx;
-            let new_y = prev_point.y + dy;
+        PushSystem.run_now(world);
+        world.maintain();
 
-            if map.in_bounds(rltk::Point::new(new_x, new_y)) {
-                let new_map_idx = map.get_index(new_x, new_y);
-                if !map.blocked_tiles[new_map_idx] {
-                    open_list.push(rltk::Point::new(dx, dy));
-                }
-            }
+        world
+            .read_storage::<crate::Position>()
+            .get(ent)
+            .unwrap()
+            .as_point()
+    }
+
+    fn two_tile_body() -> crate::MultiTile {
+        let mut symbol_map = HashMap::new();
+        symbol_map.insert(Point::new(1, 0), rltk::to_cp437('x'));
+        let part_list = vec![crate::MonsterPart {
+            symbol_map,
+            health: 1,
+            max_health: 1,
+            kind: crate::PartKind::Body,
+            hitzone: crate::Hitzone::default(),
+            discovered: vec![],
+            on_break: vec![],
+            broken: false,
+        }];
+
+        crate::MultiTile {
+            bounds: crate::all_bounds(&part_list),
+            part_list,
         }
     }
 
-    if !open_list.is_empty() {
-        open_list.sort_by(|d1, d2| {
-            let dist1 = rltk::DistanceAlg::Manhattan.distance2d(applied_force, *d1);
-            let dist2 = rltk::DistanceAlg::Manhattan.distance2d(applied_force, *d2);
-            dist1.partial_cmp(&dist2).unwrap()
-        });
+    #[test]
+    fn push_moves_more_than_one_tile() {
+        let mut world = build_world();
+        let ent = spawn(&mut world, Point::new(3, 3), None);
 
-        return Some((prev_point, *open_list.first().unwrap()));
+        assert_eq!(push(&mut world, ent, Point::new(0, 3)), Point::new(3, 6));
+
+        let map = world.fetch::<crate::Map>();
+        assert_eq!(map.creature_map.get(&map.get_index(3, 6)), Some(&ent));
+        assert!(!map.creature_map.contains_key(&map.get_index(3, 3)));
     }
 
-    // nothing open nearby, check again at one of the reflected positions
-    Some((prev_point, refl_h_force))
-}
+    #[test]
+    fn push_bounces_off_walls() {
+        let mut world = build_world();
+        let ent = spawn(&mut world, Point::new(9, 5), None);
 
-// TODO: Not even gonna think about this right now
-fn apply_forces_to_multi(
-    ent: Entity,
-    force: &crate::PushForce,
-    pos: &crate::Position,
-    multi: &crate::MultiTile,
-    map: &mut crate::Map,
-) -> rltk::Point {
-    pos.as_point()
+        // one tile into the wall, then the rest of the force bounces back
+        assert_eq!(push(&mut world, ent, Point::new(3, 0)), Point::new(8, 5));
+    }
+
+    fn health(world: &World, ent: Entity) -> i32 {
+        world
+            .read_storage::<crate::Health>()
+            .get(ent)
+            .unwrap()
+            .current
+    }
+
+    #[test]
+    fn wall_slam_deals_damage_from_remaining_force() {
+        let mut world = build_world();
+        let ent = spawn(&mut world, Point::new(9, 5), None);
+
+        assert_eq!(push(&mut world, ent, Point::new(3, 0)), Point::new(8, 5));
+        assert_eq!(health(&world, ent), 8);
+    }
+
+    #[test]
+    fn collision_pushes_the_other_creature() {
+        let mut world = build_world();
+        let ent = spawn(&mut world, Point::new(3, 5), None);
+        let other = spawn(&mut world, Point::new(4, 5), None);
+
+        assert_eq!(push(&mut world, ent, Point::new(3, 0)), Point::new(3, 5));
+        assert_eq!(health(&world, ent), 7);
+
+        let positions = world.read_storage::<crate::Position>();
+        assert_eq!(positions.get(other).unwrap().as_point(), Point::new(6, 5));
+        assert_eq!(health(&world, other), 10);
+    }
+
+    #[test]
+    fn force_runs_out_along_a_line_of_creatures() {
+        let mut world = build_world();
+        let ent = spawn(&mut world, Point::new(5, 5), None);
+        let first = spawn(&mut world, Point::new(5, 6), None);
+        let second = spawn(&mut world, Point::new(5, 7), None);
+
+        // each collision uses up one tile of force
+        assert_eq!(push(&mut world, ent, Point::new(0, 2)), Point::new(5, 5));
+        assert_eq!(health(&world, ent), 8);
+        assert_eq!(health(&world, first), 9);
+        assert_eq!(health(&world, second), 10);
+
+        let positions = world.read_storage::<crate::Position>();
+        assert_eq!(positions.get(first).unwrap().as_point(), Point::new(5, 6));
+        assert_eq!(positions.get(second).unwrap().as_point(), Point::new(5, 7));
+    }
+
+    #[test]
+    fn push_moves_every_part_of_a_multi_tile_monster() {
+        let mut world = build_world();
+        let ent = spawn(&mut world, Point::new(3, 3), Some(two_tile_body()));
+
+        assert_eq!(push(&mut world, ent, Point::new(2, 0)), Point::new(5, 3));
+
+        let map = world.fetch::<crate::Map>();
+        assert_eq!(map.creature_map.get(&map.get_index(5, 3)), Some(&ent));
+        assert_eq!(map.creature_map.get(&map.get_index(6, 3)), Some(&ent));
+        assert!(!map.creature_map.contains_key(&map.get_index(3, 3)));
+        assert!(!map.creature_map.contains_key(&map.get_index(4, 3)));
+    }
+
+    #[test]
+    fn multi_tile_monster_is_blocked_by_its_parts() {
+        let mut world = build_world();
+        let ent = spawn(&mut world, Point::new(9, 5), Some(two_tile_body()));
+
+        // the body part at x + 1 hits the wall first, so the whole monster bounces back
+        assert_eq!(push(&mut world, ent, Point::new(2, 0)), Point::new(7, 5));
+    }
 }
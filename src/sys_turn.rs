@@ -1,4 +1,5 @@
 use super::{CanActFlag, Position, RunState, Schedulable};
+use crate::StatusKind;
 use specs::prelude::*;
 
 pub struct TurnSystem;
@@ -11,8 +12,11 @@ impl<'a> System<'a> for TurnSystem {
         WriteStorage<'a, Schedulable>,
         ReadStorage<'a, Position>,
         ReadExpect<'a, Entity>,
-        WriteStorage<'a, crate::Invulnerable>,
         WriteStorage<'a, crate::Stamina>,
+        WriteStorage<'a, crate::BlockAttack>,
+        WriteStorage<'a, crate::Health>,
+        WriteStorage<'a, crate::StatusEffects>,
+        WriteStorage<'a, crate::AiState>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
@@ -23,8 +27,11 @@ impl<'a> System<'a> for TurnSystem {
             mut schedulables,
             pos,
             player,
-            mut invulns,
             mut stams,
+            mut blocks,
+            mut healths,
+            mut statuses,
+            mut ai_states,
         ) = data;
         assert!(*game_state == RunState::Running);
         if can_act.get(*player).is_some() {
@@ -32,24 +39,27 @@ impl<'a> System<'a> for TurnSystem {
             return;
         }
 
-        let mut invuln_over = Vec::new();
-
-        for (ent, sched, _pos, invuln, stam) in (
+        for (ent, sched, _pos, stam, block, health, mut status, ai_state) in (
             &entities,
             &mut schedulables,
             &pos,
-            (&mut invulns).maybe(),
             (&mut stams).maybe(),
+            (&mut blocks).maybe(),
+            (&mut healths).maybe(),
+            (&mut statuses).maybe(),
+            (&mut ai_states).maybe(),
         )
             .join()
         {
             sched.current -= sched.delta;
 
-            if let Some(invuln) = invuln {
-                invuln.duration -= 1;
+            if let Some(status) = &mut status {
+                status.tick();
+            }
 
-                if invuln.duration <= 0 {
-                    invuln_over.push(ent);
+            if let Some(block) = block {
+                if block.perfect_window > 0 {
+                    block.perfect_window -= 1;
                 }
             }
 
@@ -66,6 +76,31 @@ impl<'a> System<'a> for TurnSystem {
             }
 
             sched.current += sched.base;
+
+            // damage over time and status durations are counted at the start of each of our turns
+            if let Some(status) = status {
+                let skip_turn = status.skips_turn();
+                let (damage, expired) = status.on_turn();
+
+                if let Some(health) = health {
+                    health.current -= damage;
+                }
+
+                // monsters wake up once the sleep wears off
+                if expired.contains(&StatusKind::Sleep) {
+                    if let Some(ai_state) = ai_state {
+                        if matches!(ai_state.status, crate::Behavior::Sleep) {
+                            ai_state.status = crate::Behavior::Wander;
+                        }
+                    }
+                }
+
+                // paralyzed, stunned or sleeping entities lose their turn
+                if skip_turn {
+                    continue;
+                }
+            }
+
             can_act
                 .insert(
                     ent,
@@ -76,9 +111,5 @@ impl<'a> System<'a> for TurnSystem {
                 )
                 .expect("Failed to insert CanActFlag");
         }
-
-        for done in invuln_over {
-            invulns.remove(done);
-        }
     }
 }
@@ -13,10 +13,20 @@ impl<'a> System<'a> for VisibilitySystem {
         WriteStorage<'a, Viewable>,
         ReadStorage<'a, Position>,
         ReadStorage<'a, Player>,
+        WriteExpect<'a, crate::MissionInfo>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (mut map, entities, mut viewsheds, mut view_indexes, mut viewables, pos, player) = data;
+        let (
+            mut map,
+            entities,
+            mut viewsheds,
+            mut view_indexes,
+            mut viewables,
+            pos,
+            player,
+            mut m_info,
+        ) = data;
         let mut player_seen = Vec::new();
 
         for (ent, viewshed, pos) in (&entities, &mut viewsheds, &pos).join() {
@@ -53,6 +63,15 @@ impl<'a> System<'a> for VisibilitySystem {
             }
         }
 
+        // remember where targets were seen, even if the player hasn't moved
+        for target in m_info.remaining.clone() {
+            if let Some(target_pos) = pos.get(target) {
+                if map.visible_tiles[map.point2d_to_index(target_pos.as_point())] {
+                    m_info.last_seen.insert(target, target_pos.as_point());
+                }
+            }
+        }
+
         // only update the view index for the player's viewshed
         if player_seen.len() > 0 {
             let mut index = 0;
@@ -0,0 +1,50 @@
+use crate::{Map, TileType};
+use rltk::Point;
+use specs::prelude::*;
+
+// a map with walls around the edge and floor everywhere else
+pub fn open_map(width: i32, height: i32) -> Map {
+    let mut rng = rltk::RandomNumberGenerator::seeded(0);
+    let mut map = Map::new(
+        width,
+        height,
+        &"Test".to_string(),
+        &"#FFFFFF".to_string(),
+        &mut rng,
+    );
+    for x in 1..width - 1 {
+        for y in 1..height - 1 {
+            let index = map.get_index(x, y);
+            map.tiles[index] = TileType::Floor;
+        }
+    }
+    map.set_blocked_tiles();
+    map
+}
+
+// a world with an open map, the resources most systems expect and the player standing at player_point
+// systems under test still need to be set up on top of this
+pub fn build_world(width: i32, height: i32, player_point: Point) -> World {
+    let mut world = World::new();
+    world.register::<crate::Position>();
+
+    world.insert(open_map(width, height));
+    world.insert(rltk::RandomNumberGenerator::seeded(0));
+    world.insert(crate::ParticleBuilder::new());
+    world.insert(crate::RunState::Running);
+    world.insert(crate::gamelog::GameLog {
+        entries: vec![],
+        dirty: false,
+    });
+
+    let player = world
+        .create_entity()
+        .with(crate::Position {
+            x: player_point.x,
+            y: player_point.y,
+        })
+        .build();
+    world.insert(player);
+
+    world
+}
@@ -0,0 +1,322 @@
+use super::{Coating, Weapon, WeaponButton, WeaponUpgrades};
+use crate::data::{AttackKind, AttackOrigin, WeaponAttack, WeaponInfo, WeaponState};
+use crate::{AttackIntent, AttackType};
+
+// A weapon whose combos are described by a weapon definition in data/weapon_info.yaml
+pub struct ComboWeapon {
+    info: &'static WeaponInfo,
+    state: String,
+    upgrades: WeaponUpgrades,
+    coating: Option<Coating>,
+}
+
+fn get_attack_intent(
+    attack: &WeaponAttack,
+    from_point: rltk::Point,
+    dir: crate::Direction,
+    coating: &mut Option<Coating>,
+) -> Option<AttackIntent> {
+    let source_point = crate::direction::Direction::point_in_direction(from_point, dir);
+
+    let main = match attack.attack_type? {
+        AttackKind::Draw => AttackType::LanceDraw,
+        AttackKind::Thrust { level } => AttackType::LanceThrust {
+            level,
+            dest: crate::direction::Direction::point_in_direction(source_point, dir),
+        },
+        AttackKind::Charge => AttackType::LanceCharge { dir },
+        AttackKind::Sweep => AttackType::LanceSweep,
+        AttackKind::Slash => AttackType::GreatSwordSlash,
+        AttackKind::ChargedSlash { level, reach } => {
+            let mut dest = from_point;
+            for _ in 0..reach {
+                dest = crate::direction::Direction::point_in_direction(dest, dir);
+            }
+
+            AttackType::GreatSwordCharge { level, dest }
+        }
+        AttackKind::Shot => AttackType::BowShot {
+            dir,
+            coating: coating.take(),
+        },
+        AttackKind::PowerShot => AttackType::BowPowerShot {
+            dir,
+            coating: coating.take(),
+        },
+        AttackKind::Smash { level } => AttackType::HammerSmash { level },
+        AttackKind::Stun => AttackType::Stun,
+    };
+
+    let loc = match attack.origin {
+        AttackOrigin::Player => from_point,
+        AttackOrigin::Ahead => source_point,
+    };
+
+    Some(AttackIntent {
+        main,
+        modifier: None,
+        loc,
+        delay: attack.delay,
+    })
+}
+
+impl ComboWeapon {
+    pub fn new(name: &str) -> Self {
+        let info = crate::data::get_weapon_info(name).expect("Unknown weapon");
+
+        Self {
+            info,
+            state: info.sheathed_state.clone(),
+            upgrades: WeaponUpgrades::default(),
+            coating: None,
+        }
+    }
+
+    fn current_state(&self) -> &'static WeaponState {
+        self.info
+            .states
+            .get(&self.state)
+            .expect("Weapon is in an undefined state")
+    }
+
+    fn next_state(&self, button: WeaponButton) -> Option<(&'static WeaponAttack, &'static String)> {
+        let state = self.current_state();
+        let transition = match button {
+            WeaponButton::Light => &state.light,
+            WeaponButton::Heavy => &state.heavy,
+            WeaponButton::Special => &state.special,
+        };
+
+        transition.as_ref().map(|transition| {
+            let attack = self
+                .info
+                .attacks
+                .get(&transition.attack)
+                .expect("Weapon transition has an undefined attack");
+            (attack, &transition.next)
+        })
+    }
+
+    fn attack(
+        &mut self,
+        button: WeaponButton,
+        from: rltk::Point,
+        dir: crate::Direction,
+    ) -> Option<AttackIntent> {
+        if let Some((attack, next_state)) = self.next_state(button) {
+            self.state = next_state.clone();
+            get_attack_intent(attack, from, dir, &mut self.coating)
+        } else {
+            None
+        }
+    }
+}
+
+impl Weapon for ComboWeapon {
+    fn base_name(&self) -> String {
+        self.info
+            .short_name
+            .as_ref()
+            .unwrap_or(&self.info.name)
+            .clone()
+    }
+
+    fn sheathe(&mut self) -> bool {
+        if self.state == self.info.sheathed_state {
+            return false;
+        }
+
+        self.state = self.info.sheathed_state.clone();
+        true
+    }
+
+    fn reset(&mut self) {
+        if self.state != self.info.sheathed_state {
+            self.state = self.info.ready_state.clone();
+        }
+    }
+
+    fn can_guard(&self) -> bool {
+        self.info.states.values().any(|state| state.guard.is_some())
+    }
+
+    fn guard(&mut self) -> bool {
+        match &self.current_state().guard {
+            None => false,
+            Some(guard_state) => {
+                self.state = guard_state.clone();
+                true
+            }
+        }
+    }
+
+    fn block_amount(&self) -> Option<u32> {
+        self.current_state()
+            .block
+            .map(|block| block + self.upgrades.level() as u32)
+    }
+
+    fn counter(&mut self) {
+        if let Some(counter_state) = &self.current_state().counter {
+            self.state = counter_state.clone();
+        }
+    }
+
+    fn upgrades(&self) -> WeaponUpgrades {
+        self.upgrades
+    }
+
+    fn upgrades_mut(&mut self) -> &mut WeaponUpgrades {
+        &mut self.upgrades
+    }
+
+    fn attack_bonus(&self) -> i32 {
+        self.info.attack + self.upgrades.damage_bonus()
+    }
+
+    fn charge_level(&self) -> Option<u8> {
+        self.current_state().charge
+    }
+
+    fn interrupt(&mut self) {
+        if self.charge_level().is_some() {
+            self.state = self.info.ready_state.clone();
+        }
+    }
+
+    fn load_coating(&mut self, coating: Coating) -> bool {
+        if !self.info.coatings || self.coating.is_some() {
+            return false;
+        }
+
+        self.coating = Some(coating);
+        true
+    }
+
+    fn light_attack(&mut self, from: rltk::Point, dir: crate::Direction) -> Option<AttackIntent> {
+        self.attack(WeaponButton::Light, from, dir)
+    }
+
+    fn heavy_attack(&mut self, from: rltk::Point, dir: crate::Direction) -> Option<AttackIntent> {
+        self.attack(WeaponButton::Heavy, from, dir)
+    }
+
+    fn special_attack(&mut self, from: rltk::Point, dir: crate::Direction) -> Option<AttackIntent> {
+        self.attack(WeaponButton::Special, from, dir)
+    }
+
+    fn can_activate_cost(&self, button: WeaponButton) -> Option<i32> {
+        self.next_state(button)
+            .map(|(attack, _)| self.upgrades.stamina_cost(attack.stamina))
+    }
+
+    fn attack_name(&self, button: WeaponButton) -> Option<String> {
+        self.next_state(button).map(|(attack, _)| {
+            self.coating
+                .and_then(|coating| attack.coated_names.get(&coating))
+                .unwrap_or(&attack.name)
+                .clone()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Direction;
+    use rltk::Point;
+
+    #[test]
+    fn lance_combo_runs_from_data() {
+        let mut lance = ComboWeapon::new("Lance");
+        let from = Point::new(5, 5);
+        assert!(lance.can_guard());
+
+        let draw = lance.light_attack(from, Direction::N).unwrap();
+        assert_eq!(draw.main, AttackType::LanceDraw);
+        assert_eq!(draw.loc, Point::new(5, 4));
+
+        let thrust = lance.light_attack(from, Direction::N).unwrap();
+        assert_eq!(
+            thrust.main,
+            AttackType::LanceThrust {
+                level: 2,
+                dest: Point::new(5, 3)
+            }
+        );
+        assert_eq!(
+            lance.attack_name(WeaponButton::Light),
+            Some("Stab III".to_string())
+        );
+    }
+
+    #[test]
+    fn great_sword_charges_until_released_or_interrupted() {
+        let mut sword = ComboWeapon::new("Great Sword");
+        let from = Point::new(5, 5);
+        assert_eq!(sword.base_name(), "G.Sword");
+        assert!(!sword.can_guard());
+
+        let draw = sword.light_attack(from, Direction::N).unwrap();
+        assert_eq!(draw.main, AttackType::GreatSwordSlash);
+        assert!(sword.heavy_attack(from, Direction::N).is_none());
+        assert_eq!(sword.charge_level(), Some(1));
+        sword.interrupt();
+        assert_eq!(sword.charge_level(), None);
+
+        sword.heavy_attack(from, Direction::N);
+        sword.heavy_attack(from, Direction::N);
+        assert_eq!(
+            sword.light_attack(from, Direction::N).unwrap().main,
+            AttackType::GreatSwordCharge {
+                level: 2,
+                dest: Point::new(5, 3)
+            }
+        );
+        assert_eq!(sword.charge_level(), None);
+    }
+
+    #[test]
+    fn bow_arrows_use_up_the_loaded_coating() {
+        let mut bow = ComboWeapon::new("Bow");
+        let from = Point::new(5, 5);
+        bow.light_attack(from, Direction::N);
+
+        assert!(bow.load_coating(Coating::Poison));
+        assert!(!bow.load_coating(Coating::Sleep));
+        assert_eq!(
+            bow.attack_name(WeaponButton::Light),
+            Some("Poison Shot".to_string())
+        );
+
+        let shot = bow.light_attack(from, Direction::N).unwrap();
+        assert_eq!(
+            shot.main,
+            AttackType::BowShot {
+                dir: Direction::N,
+                coating: Some(Coating::Poison)
+            }
+        );
+        assert_eq!(
+            bow.attack_name(WeaponButton::Light),
+            Some("Shoot".to_string())
+        );
+    }
+
+    #[test]
+    fn hammer_smashes_build_up_to_a_bash() {
+        let mut hammer = ComboWeapon::new("Hammer");
+        let from = Point::new(5, 5);
+
+        let draw = hammer.light_attack(from, Direction::E).unwrap();
+        assert_eq!(draw.main, AttackType::HammerSmash { level: 1 });
+        assert_eq!(draw.loc, Point::new(6, 5));
+
+        hammer.light_attack(from, Direction::E);
+        hammer.light_attack(from, Direction::E);
+        assert_eq!(hammer.can_activate_cost(WeaponButton::Light), None);
+
+        let bash = hammer.heavy_attack(from, Direction::E).unwrap();
+        assert_eq!(bash.main, AttackType::Stun);
+    }
+}
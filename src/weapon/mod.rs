@@ -1,4 +1,28 @@
-pub mod lance;
+use derivative::Derivative;
+use serde::Deserialize;
+
+pub mod combo;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Derivative, Deserialize)]
+#[derivative(Hash)]
+pub enum Coating {
+    Power,
+    Poison,
+    Paralysis,
+    Sleep,
+}
+
+impl Coating {
+    pub fn name(&self) -> String {
+        match self {
+            Coating::Power => "Power Coating",
+            Coating::Poison => "Poison Coating",
+            Coating::Paralysis => "Para Coating",
+            Coating::Sleep => "Sleep Coating",
+        }
+        .to_string()
+    }
+}
 
 pub enum WeaponButton {
     Light,
@@ -6,12 +30,61 @@ pub enum WeaponButton {
     Special,
 }
 
+// improvements bought from the blacksmith
+#[derive(Copy, Clone, Default)]
+pub struct WeaponUpgrades {
+    pub sharpness: u8,
+    pub handling: u8,
+}
+
+impl WeaponUpgrades {
+    pub fn level(&self) -> u8 {
+        self.sharpness + self.handling
+    }
+
+    pub fn damage_bonus(&self) -> i32 {
+        self.sharpness as i32
+    }
+
+    // better handling makes attacks cheaper, but never free
+    pub fn stamina_cost(&self, cost: i32) -> i32 {
+        if cost > 1 {
+            std::cmp::max(1, cost - self.handling as i32)
+        } else {
+            cost
+        }
+    }
+}
+
 pub trait Weapon {
-    fn name(&self) -> String;
+    fn base_name(&self) -> String;
+
+    fn name(&self) -> String {
+        format!("{}+{}", self.base_name(), self.upgrades().level())
+    }
+
+    fn upgrades(&self) -> WeaponUpgrades;
+    fn upgrades_mut(&mut self) -> &mut WeaponUpgrades;
+
+    // extra damage added to each attack
+    fn attack_bonus(&self) -> i32 {
+        self.upgrades().damage_bonus()
+    }
 
     fn sheathe(&mut self) -> bool;
     fn reset(&mut self);
 
+    // whether the weapon has any guard at all
+    fn can_guard(&self) -> bool;
+    fn guard(&mut self) -> bool;
+    fn block_amount(&self) -> Option<u32>;
+    fn counter(&mut self);
+
+    fn charge_level(&self) -> Option<u8>;
+    fn interrupt(&mut self);
+
+    fn load_coating(&mut self, coating: Coating) -> bool;
+
     fn light_attack(
         &mut self,
         from: rltk::Point,
@@ -31,3 +104,7 @@ pub trait Weapon {
     fn can_activate_cost(&self, button: WeaponButton) -> Option<i32>;
     fn attack_name(&self, button: WeaponButton) -> Option<String>;
 }
+
+pub fn build_weapon(name: &str) -> Box<dyn Weapon> {
+    Box::new(combo::ComboWeapon::new(name))
+}